@@ -1,64 +1,51 @@
-use cstr_core::CString;
-use embedded_graphics::pixelcolor::Rgb565;
-use embedded_graphics::prelude::*;
+use embedded_graphics_core::prelude::*;
 use embedded_graphics_simulator::{
     OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
 };
-use lvgl;
-use lvgl::style::Style;
-use lvgl::widgets::{Label, LabelAlign};
-use lvgl::{Align, Color, LvError, Part, State, Widget, UI};
-use lvgl_sys;
+use lvgl::core::{Display, Lvgl, ObjExt, PixelColor, Screen};
+use lvgl::font::MONTSERRAT_48;
+use lvgl::style::{Align, Part, State};
+use lvgl::widgets::Label;
+use std::mem::MaybeUninit;
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
-fn main() -> Result<(), LvError> {
-    let display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(
-        lvgl_sys::LV_HOR_RES_MAX,
-        lvgl_sys::LV_VER_RES_MAX,
-    ));
+// The screen of the PineTime
+const WIDTH: u32 = 240;
+const HEIGHT: u32 = 240;
 
-    let output_settings = OutputSettingsBuilder::new().scale(2).build();
-    let mut window = Window::new("PineTime", &output_settings);
+fn main() {
+    let mut lvgl = Lvgl::new();
 
-    let mut ui = UI::init()?;
+    let draw_buffer = vec![MaybeUninit::uninit(); (WIDTH * HEIGHT / 10) as usize];
+    let mut display = Display::new(
+        &lvgl,
+        SimulatorDisplay::<PixelColor>::new(Size::new(WIDTH, HEIGHT)),
+        draw_buffer.leak(),
+    );
 
-    // Implement and register your display:
-    ui.disp_drv_register(display).unwrap();
+    let output_settings = OutputSettingsBuilder::new().scale(2).build();
+    let mut window = Window::new("PineTime", &output_settings);
 
-    // Create screen and widgets
-    let mut screen = ui.scr_act()?;
+    let mut screen = Screen::<()>::new(&display);
 
-    let mut screen_style = Style::default();
-    screen_style.set_bg_color(State::DEFAULT, Color::from_rgb((0, 0, 0)));
-    screen_style.set_radius(State::DEFAULT, 0);
-    screen.add_style(Part::Main, screen_style)?;
+    let mut time = Label::new(&mut screen);
+    time.set_style_text_font(MONTSERRAT_48, Part::Main, State::DEFAULT);
+    time.set_text("20:46");
+    time.align_to(&screen, Align::Center, 0, 0);
 
-    let mut time = Label::new(&mut screen)?;
-    let mut style_time = Style::default();
-    //style_time.set_text_font(font_noto_sans_numeric_28);
-    style_time.set_text_color(State::DEFAULT, Color::from_rgb((255, 255, 255)));
-    time.add_style(Part::Main, style_time)?;
-    time.set_align(&mut screen, Align::Center, 0, 0)?;
-    time.set_text(CString::new("20:46").unwrap().as_c_str())?;
-    time.set_width(240)?;
-    time.set_height(240)?;
+    // Recolored with `#rrggbb text#`
+    let mut bt = Label::new(&mut screen);
+    bt.set_recolor(true);
+    bt.set_text("#5794f2 \u{F293}#");
+    bt.align_to(&screen, Align::TopLeft, 0, 0);
 
-    let mut bt = Label::new(&mut screen)?;
-    bt.set_width(50)?;
-    bt.set_height(80)?;
-    bt.set_recolor(true)?;
-    bt.set_text(CString::new("#5794f2 \u{F293}#").unwrap().as_c_str())?;
-    bt.set_label_align(LabelAlign::Left)?;
-    bt.set_align(&mut screen, Align::InTopLeft, 0, 0)?;
+    let mut power = Label::new(&mut screen);
+    power.set_recolor(true);
+    power.set_text("#fade2a 20%#");
+    power.align_to(&screen, Align::TopRight, 0, 0);
 
-    let mut power = Label::new(&mut screen)?;
-    power.set_recolor(true)?;
-    power.set_width(80)?;
-    power.set_height(20)?;
-    power.set_text(CString::new("#fade2a 20%#").unwrap().as_c_str())?;
-    power.set_label_align(LabelAlign::Right)?;
-    power.set_align(&mut screen, Align::InTopRight, 0, 0)?;
+    display.load_screen(&mut screen);
 
     let mut i = 0;
     let mut loop_started = Instant::now();
@@ -66,36 +53,24 @@ fn main() -> Result<(), LvError> {
         if i > 59 {
             i = 0;
         }
-        let val = CString::new(format!("21:{:02}", i)).unwrap();
-        time.set_text(&val)?;
-        i = 1 + i;
+        // The text is copied by LVGL, no need to keep it around
+        time.set_text(format!("21:{:02}", i));
+        i += 1;
 
-        ui.task_handler();
-        window.update(ui.get_display_ref().unwrap());
+        lvgl.run_tasks();
+        window.update(&display);
 
         for event in window.events() {
-            match event {
-                SimulatorEvent::Quit => break 'running,
-                _ => {}
+            if let SimulatorEvent::Quit = event {
+                break 'running;
             }
         }
         sleep(Duration::from_secs(1));
 
-        ui.tick_inc(loop_started.elapsed());
+        lvgl.ticks().inc(loop_started.elapsed().as_millis() as u32);
         loop_started = Instant::now();
     }
 
-    Ok(())
-}
-
-// Reference to native font for LVGL, defined in the file: "fonts_noto_sans_numeric_80.c"
-// TODO: Create a macro for defining a safe wrapper for fonts.
-// Maybe sometihng like:
-//
-// font_declare! {
-//     NotoSansNumeric80 = noto_sans_numeric_80;
-// };
-//
-extern "C" {
-    pub static mut noto_sans_numeric_80: lvgl_sys::lv_font_t;
+    // Displays are never unregistered
+    std::mem::forget(display);
 }
@@ -1,66 +1,77 @@
-use embedded_graphics::pixelcolor::Rgb565;
-use embedded_graphics::prelude::*;
+use embedded_graphics_core::{pixelcolor::RgbColor, prelude::*};
 use embedded_graphics_simulator::{
     OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window,
 };
-use lvgl::style::{Opacity, Style};
-use lvgl::widgets::Gauge;
-use lvgl::{self, Align, Color, LvError, Part, State, Widget, UI};
-use lvgl_sys;
+use lvgl::core::{Display, Lvgl, ObjExt, PixelColor, Screen};
+use lvgl::style::Align;
+use lvgl::widgets::Meter;
+use std::mem::MaybeUninit;
 use std::time::Instant;
 
-fn main() -> Result<(), LvError> {
-    let display: SimulatorDisplay<Rgb565> = SimulatorDisplay::new(Size::new(
-        lvgl_sys::LV_HOR_RES_MAX,
-        lvgl_sys::LV_VER_RES_MAX,
-    ));
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+
+fn main() {
+    let mut lvgl = Lvgl::new();
+
+    // 1/10th of the screen for the draw buffer
+    let draw_buffer = vec![MaybeUninit::uninit(); (WIDTH * HEIGHT / 10) as usize];
+    let mut display = Display::new(
+        &lvgl,
+        SimulatorDisplay::<PixelColor>::new(Size::new(WIDTH, HEIGHT)),
+        draw_buffer.leak(),
+    );
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
     let mut window = Window::new("Gauge Example", &output_settings);
 
-    let mut ui = UI::init()?;
+    let mut screen = Screen::<()>::new(&display);
+
+    // Create the gauge
+    let mut gauge = Meter::new(&mut screen);
+    gauge.set_size(200, 200);
+    gauge.align_to(&screen, Align::Center, 0, 0);
 
-    // Implement and register your display:
-    ui.disp_drv_register(display)?;
+    // A scale from 0 to 100 over 270 degrees, with a label every 20
+    let mut scale = gauge.add_scale();
+    gauge
+        .set_scale_ticks(&mut scale, 41, 2, 10, PixelColor::WHITE)
+        .set_scale_major_ticks(&mut scale, 8, 4, 15, PixelColor::BLACK, 10)
+        .set_scale_range(&mut scale, 0, 100, 270, 135);
 
-    // Create screen and widgets
-    let mut screen = ui.scr_act()?;
+    // Blue at the start of the scale, red at the end
+    let mut low = gauge.add_arc(&mut scale, 3, PixelColor::BLUE, 0);
+    gauge
+        .set_indicator_start_value(&mut low, 0)
+        .set_indicator_end_value(&mut low, 20);
+    let mut low_ticks =
+        gauge.add_scale_lines(&mut scale, PixelColor::BLUE, PixelColor::BLUE, false, 0);
+    gauge
+        .set_indicator_start_value(&mut low_ticks, 0)
+        .set_indicator_end_value(&mut low_ticks, 20);
 
-    let mut screen_style = Style::default();
-    screen_style.set_bg_color(State::DEFAULT, Color::from_rgb((0, 0, 0)));
-    screen.add_style(Part::Main, screen_style)?;
+    let mut high = gauge.add_arc(&mut scale, 3, PixelColor::RED, 0);
+    gauge
+        .set_indicator_start_value(&mut high, 80)
+        .set_indicator_end_value(&mut high, 100);
+    let mut high_ticks =
+        gauge.add_scale_lines(&mut scale, PixelColor::RED, PixelColor::RED, false, 0);
+    gauge
+        .set_indicator_start_value(&mut high_ticks, 80)
+        .set_indicator_end_value(&mut high_ticks, 100);
 
-    // Create the gauge
-    let mut gauge_style = Style::default();
-    // Set a background color and a radius
-    gauge_style.set_radius(State::DEFAULT, 5);
-    gauge_style.set_bg_opa(State::DEFAULT, Opacity::OPA_COVER);
-    gauge_style.set_bg_color(State::DEFAULT, Color::from_rgb((192, 192, 192)));
-    // Set some paddings
-    gauge_style.set_pad_inner(State::DEFAULT, 20);
-    gauge_style.set_pad_top(State::DEFAULT, 20);
-    gauge_style.set_pad_left(State::DEFAULT, 5);
-    gauge_style.set_pad_right(State::DEFAULT, 5);
-
-    gauge_style.set_scale_end_color(State::DEFAULT, Color::from_rgb((255, 0, 0)));
-    gauge_style.set_line_color(State::DEFAULT, Color::from_rgb((255, 255, 255)));
-    gauge_style.set_scale_grad_color(State::DEFAULT, Color::from_rgb((0, 0, 255)));
-    gauge_style.set_line_width(State::DEFAULT, 2);
-    gauge_style.set_scale_end_line_width(State::DEFAULT, 4);
-    gauge_style.set_scale_end_border_width(State::DEFAULT, 4);
-
-    let mut gauge = Gauge::new(&mut screen)?;
-    gauge.add_style(Part::Main, gauge_style)?;
-    gauge.set_align(&mut screen, Align::Center, 0, 0)?;
-    gauge.set_value(0, 50)?;
+    let mut needle = gauge.add_needle_line(&mut scale, 4, PixelColor::BLACK, -10);
+    gauge.set_indicator_value(&mut needle, 50);
+
+    display.load_screen(&mut screen);
 
     let mut i = 0;
     let mut loop_started = Instant::now();
     'running: loop {
-        gauge.set_value(0, i)?;
+        gauge.set_indicator_value(&mut needle, i);
 
-        ui.task_handler();
-        window.update(ui.get_display_ref().unwrap());
+        lvgl.run_tasks();
+        window.update(&display);
 
         for event in window.events() {
             match event {
@@ -81,9 +92,10 @@ fn main() -> Result<(), LvError> {
             i = i + 1;
         }
 
-        ui.tick_inc(loop_started.elapsed());
+        lvgl.ticks().inc(loop_started.elapsed().as_millis() as u32);
         loop_started = Instant::now();
     }
 
-    Ok(())
+    // Displays are never unregistered
+    std::mem::forget(display);
 }
@@ -0,0 +1,309 @@
+//! Converts TTF/OTF fonts into LVGL bitmap fonts, without the `lv_font_conv` tool.
+//!
+//! Meant to be used from a `build.rs`, the generated code is a `lvgl::font::FontData`
+//! static:
+//!
+//! ```ignore
+//! let font = BitmapFont::rasterize(
+//!     "NOTO_SANS_NUMERIC_80",
+//!     include_bytes!("fonts/NotoSans-Regular.ttf"),
+//!     80.0,
+//!     4,
+//!     &['0'..='9', ':'..=':'],
+//! )?;
+//! std::fs::write(out_path.join("fonts.rs"), font.code(&()).unwrap().to_string())?;
+//! ```
+
+use crate::{CGResult, Rusty, WrapperResult};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+/// A glyph of the font, in the layout of `lv_font_fmt_txt_glyph_dsc_t`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Glyph {
+    /// Start of the glyph in the bitmap
+    pub bitmap_index: u32,
+    /// Advance width, in 1/16 px
+    pub adv_w: u16,
+    pub box_w: u8,
+    pub box_h: u8,
+    pub ofs_x: i8,
+    pub ofs_y: i8,
+}
+
+/// Consecutive code points mapped to consecutive glyphs, i.e. a `LV_FONT_FMT_TXT_CMAP_FORMAT0_TINY`
+/// character map
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphRange {
+    pub start: u32,
+    pub len: u16,
+    pub glyph_id_start: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct BitmapFont {
+    name: String,
+    line_height: i16,
+    base_line: i16,
+    underline_position: i8,
+    underline_thickness: i8,
+    bpp: u8,
+    bitmap: Vec<u8>,
+    glyphs: Vec<Glyph>,
+    ranges: Vec<GlyphRange>,
+}
+
+impl BitmapFont {
+    /// Rasterizes the characters of `chars` found in the font, at `size` px and `bpp` bits
+    /// per pixel (1, 2, 4 or 8). `name` is the name of the generated static.
+    pub fn rasterize(
+        name: &str,
+        font_data: &[u8],
+        size: f32,
+        bpp: u8,
+        chars: &[RangeInclusive<char>],
+    ) -> CGResult<Self> {
+        if ![1, 2, 4, 8].contains(&bpp) {
+            return Err(format!("Unsupported bpp: {}", bpp).into());
+        }
+        let font = fontdue::Font::from_bytes(font_data, fontdue::FontSettings::default())?;
+        let line_metrics = font
+            .horizontal_line_metrics(size)
+            .ok_or("The font has no horizontal line metrics")?;
+
+        let mut chars: Vec<char> = chars.iter().cloned().flatten().collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut bitmap = Vec::new();
+        // Glyph id 0 is reserved by LVGL
+        let mut glyphs = vec![Glyph::default()];
+        let mut ranges: Vec<GlyphRange> = Vec::new();
+        let too_large = || format!("The font is too large for LVGL at {} px", size);
+        for c in chars
+            .into_iter()
+            .filter(|c| font.lookup_glyph_index(*c) != 0)
+        {
+            let (metrics, coverage) = font.rasterize(c, size);
+            let adv_w = (metrics.advance_width * 16.0).round() as u32;
+            if adv_w >= 1 << 12 || bitmap.len() >= 1 << 20 {
+                return Err(too_large().into());
+            }
+
+            glyphs.push(Glyph {
+                bitmap_index: bitmap.len() as u32,
+                adv_w: adv_w as u16,
+                box_w: u8::try_from(metrics.width).map_err(|_| too_large())?,
+                box_h: u8::try_from(metrics.height).map_err(|_| too_large())?,
+                ofs_x: i8::try_from(metrics.xmin).map_err(|_| too_large())?,
+                ofs_y: i8::try_from(metrics.ymin).map_err(|_| too_large())?,
+            });
+            bitmap.extend(pack_bitmap(&coverage, bpp));
+
+            let glyph_id = u16::try_from(glyphs.len() - 1).map_err(|_| "Too many glyphs")?;
+            match ranges.last_mut() {
+                Some(range) if range.start + range.len as u32 == c as u32 => range.len += 1,
+                _ => ranges.push(GlyphRange {
+                    start: c as u32,
+                    len: 1,
+                    glyph_id_start: glyph_id,
+                }),
+            }
+        }
+
+        let ascent = line_metrics.ascent.ceil() as i16;
+        let descent = line_metrics.descent.floor() as i16;
+        Ok(Self {
+            name: name.to_string(),
+            line_height: ascent - descent,
+            base_line: -descent,
+            // fontdue doesn't expose the underline metrics of the font
+            underline_position: -((size / 10.0).round() as i8),
+            underline_thickness: ((size / 20.0).round() as i8).max(1),
+            bpp,
+            bitmap,
+            glyphs,
+            ranges,
+        })
+    }
+
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    pub fn ranges(&self) -> &[GlyphRange] {
+        &self.ranges
+    }
+
+    pub fn bitmap(&self) -> &[u8] {
+        &self.bitmap
+    }
+}
+
+impl Rusty for BitmapFont {
+    type Parent = ();
+
+    fn code(&self, _parent: &Self::Parent) -> WrapperResult<TokenStream> {
+        let name = format_ident!("{}", self.name);
+        // lv_coord_t is either i16 or i32, depending on LV_USE_LARGE_COORD
+        let line_height = Literal::i16_unsuffixed(self.line_height);
+        let base_line = Literal::i16_unsuffixed(self.base_line);
+        let underline_position = self.underline_position;
+        let underline_thickness = self.underline_thickness;
+        let bpp = self.bpp;
+        let bitmap = Literal::byte_string(&self.bitmap);
+        let glyphs = self.glyphs.iter().map(|g| {
+            let (bitmap_index, adv_w, box_w, box_h, ofs_x, ofs_y) =
+                (g.bitmap_index, g.adv_w, g.box_w, g.box_h, g.ofs_x, g.ofs_y);
+            quote! {
+                ::lvgl::font::GlyphData {
+                    bitmap_index: #bitmap_index,
+                    adv_w: #adv_w,
+                    box_w: #box_w,
+                    box_h: #box_h,
+                    ofs_x: #ofs_x,
+                    ofs_y: #ofs_y,
+                }
+            }
+        });
+        let ranges = self.ranges.iter().map(|r| {
+            let (start, len, glyph_id_start) = (r.start, r.len, r.glyph_id_start);
+            quote! {
+                ::lvgl::font::GlyphRange {
+                    start: #start,
+                    len: #len,
+                    glyph_id_start: #glyph_id_start,
+                }
+            }
+        });
+
+        Ok(quote! {
+            pub static #name: ::lvgl::font::FontData = ::lvgl::font::FontData::new(
+                ::lvgl::font::FontMetrics {
+                    line_height: #line_height,
+                    base_line: #base_line,
+                    underline_position: #underline_position,
+                    underline_thickness: #underline_thickness,
+                    bpp: #bpp,
+                },
+                #bitmap,
+                &[#(#glyphs),*],
+                &[#(#ranges),*],
+            );
+        })
+    }
+}
+
+/// Packs 8 bit coverage values into `bpp` bits per pixel, most significant bits first.
+/// Rows are not padded, as expected by LVGL.
+fn pack_bitmap(coverage: &[u8], bpp: u8) -> Vec<u8> {
+    let max = (1u32 << bpp) - 1;
+    let mut packed = Vec::with_capacity((coverage.len() * bpp as usize).div_ceil(8));
+    let mut byte = 0u8;
+    let mut bits = 0;
+    for &c in coverage {
+        let value = ((c as u32 * max + 127) / 255) as u8;
+        byte |= value << (8 - bpp - bits);
+        bits += bpp;
+        if bits == 8 {
+            packed.push(byte);
+            byte = 0;
+            bits = 0;
+        }
+    }
+    if bits > 0 {
+        packed.push(byte);
+    }
+    packed
+}
+
+#[cfg(test)]
+mod test {
+    use super::{pack_bitmap, BitmapFont, Glyph, GlyphRange};
+    use crate::Rusty;
+    use quote::quote;
+
+    // From ttf-parser, a single glyph: 'A', 541 units wide and 656 high, with 1000 units per em
+    const DEMO_TTF: &[u8] = include_bytes!("../fonts/demo.ttf");
+
+    #[test]
+    fn rasterize_glyph_metrics() {
+        // 'B' is not in the font, so it is left out
+        let font = BitmapFont::rasterize("DEMO_20", DEMO_TTF, 20.0, 4, &['A'..='B']).unwrap();
+        assert_eq!(
+            font.glyphs(),
+            [
+                Glyph::default(),
+                Glyph {
+                    bitmap_index: 0,
+                    // 540 units, i.e. 10.8 px
+                    adv_w: 173,
+                    box_w: 11,
+                    box_h: 14,
+                    ofs_x: 0,
+                    ofs_y: 0,
+                },
+            ]
+        );
+        assert_eq!(
+            font.ranges(),
+            [GlyphRange {
+                start: 'A' as u32,
+                len: 1,
+                glyph_id_start: 1,
+            }]
+        );
+        // 11 x 14 pixels at 4 bits per pixel
+        assert_eq!(font.bitmap().len(), 77);
+        assert_eq!(font.line_height, 29);
+        assert_eq!(font.base_line, 8);
+
+        let code = font.code(&()).unwrap().to_string();
+        let name = quote!(pub static DEMO_20: ::lvgl::font::FontData).to_string();
+        assert!(code.starts_with(&name), "{}", code);
+        let glyph = quote! {
+            ::lvgl::font::GlyphData {
+                bitmap_index: 0u32,
+                adv_w: 173u16,
+                box_w: 11u8,
+                box_h: 14u8,
+                ofs_x: 0i8,
+                ofs_y: 0i8,
+            }
+        };
+        assert!(code.contains(&glyph.to_string()), "{}", code);
+        let range = quote! {
+            ::lvgl::font::GlyphRange {
+                start: 65u32,
+                len: 1u16,
+                glyph_id_start: 1u16,
+            }
+        };
+        assert!(code.contains(&range.to_string()), "{}", code);
+    }
+
+    #[test]
+    fn rasterize_rejects_glyphs_too_large_for_lvgl() {
+        // 'A' would be 263 px high, more than the u8 of the glyph descriptors
+        assert!(BitmapFont::rasterize("DEMO_400", DEMO_TTF, 400.0, 1, &['A'..='A']).is_err());
+        assert!(BitmapFont::rasterize("DEMO_300", DEMO_TTF, 300.0, 1, &['A'..='A']).is_ok());
+    }
+
+    #[test]
+    fn rasterize_rejects_unsupported_bpp() {
+        assert!(BitmapFont::rasterize("DEMO", DEMO_TTF, 20.0, 3, &['A'..='A']).is_err());
+    }
+
+    #[test]
+    fn pack_bitmap_msb_first() {
+        assert_eq!(
+            pack_bitmap(&[255, 0, 255, 0, 255, 0, 255, 0, 255], 1),
+            vec![0b1010_1010, 0b1000_0000]
+        );
+        assert_eq!(pack_bitmap(&[255, 0, 128], 4), vec![0xf0, 0x80]);
+        assert_eq!(pack_bitmap(&[255, 85, 170, 0], 2), vec![0b1101_1000]);
+        assert_eq!(pack_bitmap(&[12, 200], 8), vec![12, 200]);
+    }
+}
@@ -4,14 +4,76 @@ use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use quote::{format_ident, ToTokens};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use syn::{FnArg, ForeignItem, ForeignItemFn, Item, ItemFn, ReturnType};
 
+#[cfg(feature = "font")]
+pub mod font;
+
 type CGResult<T> = Result<T, Box<dyn Error>>;
 
 const LIB_PREFIX: &str = "lv_";
 
+/// Widgets that can be turned off in `lv_conf.h`, with their `LV_USE_*` option. A disabled
+/// widget has no functions in the bindings, so this is how we know it exists at all.
+const CONFIGURABLE_WIDGETS: &[(&str, &str)] = &[
+    ("animimg", "LV_USE_ANIMIMG"),
+    ("arc", "LV_USE_ARC"),
+    ("bar", "LV_USE_BAR"),
+    ("btn", "LV_USE_BTN"),
+    ("btnmatrix", "LV_USE_BTNMATRIX"),
+    ("calendar", "LV_USE_CALENDAR"),
+    ("canvas", "LV_USE_CANVAS"),
+    ("chart", "LV_USE_CHART"),
+    ("checkbox", "LV_USE_CHECKBOX"),
+    ("dropdown", "LV_USE_DROPDOWN"),
+    ("gif", "LV_USE_GIF"),
+    ("img", "LV_USE_IMG"),
+    ("imgbtn", "LV_USE_IMGBTN"),
+    ("keyboard", "LV_USE_KEYBOARD"),
+    ("label", "LV_USE_LABEL"),
+    ("led", "LV_USE_LED"),
+    ("line", "LV_USE_LINE"),
+    ("list", "LV_USE_LIST"),
+    ("menu", "LV_USE_MENU"),
+    ("meter", "LV_USE_METER"),
+    ("roller", "LV_USE_ROLLER"),
+    ("slider", "LV_USE_SLIDER"),
+    ("spangroup", "LV_USE_SPAN"),
+    ("spinbox", "LV_USE_SPINBOX"),
+    ("switch", "LV_USE_SWITCH"),
+    ("table", "LV_USE_TABLE"),
+    ("textarea", "LV_USE_TEXTAREA"),
+    ("tileview", "LV_USE_TILEVIEW"),
+];
+
+/// Functions wrapped by hand in `lvgl::widgets`, because their generated wrapper would be
+/// unsafe or clash with the hand-written one.
+const SKIPPED_FUNCTIONS: &[&str] = &[
+    "lv_calendar_set_showed_date",
+    "lv_calendar_set_today_date",
+    "lv_canvas_fill_bg",
+    "lv_canvas_set_px",
+    "lv_canvas_set_px_color",
+    "lv_chart_set_point_count",
+    "lv_dropdown_set_options",
+    "lv_dropdown_set_selected",
+    "lv_roller_set_options",
+    "lv_roller_set_selected",
+    "lv_textarea_set_accepted_chars",
+    "lv_textarea_set_insert_replace",
+];
+
+/// Functions after which LVGL keeps pointing to their string arguments, so these must be
+/// `'static`. The strings of the others are copied.
+const STATIC_STR_FUNCTIONS: &[&str] = &[
+    "lv_checkbox_set_text_static",
+    "lv_dropdown_set_options_static",
+    "lv_dropdown_set_text",
+    "lv_label_set_text_static",
+];
+
 lazy_static! {
     static ref TYPE_MAPPINGS: HashMap<&'static str, &'static str> = [
         ("i16", "i16"),
@@ -25,24 +87,25 @@ lazy_static! {
         ("lv_anim_enable_t", "lv_anim_enable_t"),
         ("lv_arc_mode_t", "lv_arc_mode_t"),
         ("lv_bar_mode_t", "lv_bar_mode_t"),
-        ("lv_btnmatrix_ctrl_t", "lv_btnmatrix_ctrl_t"),
-        ("lv_chart_axis_t", "lv_chart_axis_t"),
-        ("lv_chart_type_t", "lv_chart_type_t"),
-        ("lv_chart_update_mode_t", "lv_chart_update_mode_t"),
+        // Rust types are converted with `.into()`
+        ("lv_btnmatrix_ctrl_t", "crate::widgets::BtnmatrixCtrl"),
+        ("lv_chart_axis_t", "crate::widgets::ChartAxis"),
+        ("lv_chart_type_t", "crate::widgets::ChartType"),
+        ("lv_chart_update_mode_t", "crate::widgets::ChartUpdateMode"),
         ("lv_color_t", "lv_color_t"),
         ("lv_coord_t", "lv_coord_t"),
-        ("lv_dir_t", "lv_dir_t"),
+        ("lv_dir_t", "crate::style::Dir"),
         ("lv_img_size_mode_t", "lv_img_size_mode_t"),
         ("lv_imgbtn_state_t", "lv_imgbtn_state_t"),
-        ("lv_keyboard_mode_t", "lv_keyboard_mode_t"),
-        ("lv_label_long_mode_t", "lv_label_long_mode_t"),
+        ("lv_keyboard_mode_t", "crate::widgets::KeyboardMode"),
+        ("lv_label_long_mode_t", "crate::widgets::LongMode"),
         ("lv_menu_mode_header_t", "lv_menu_mode_header_t"),
         ("lv_menu_mode_root_back_btn_t", "lv_menu_mode_root_back_btn_t"),
-        ("lv_roller_mode_t", "lv_roller_mode_t"),
+        ("lv_roller_mode_t", "crate::widgets::RollerMode"),
         ("lv_slider_mode_t", "lv_slider_mode_t"),
         ("lv_span_mode_t", "lv_span_mode_t"),
         ("lv_span_overflow_t", "lv_span_overflow_t"),
-        ("lv_table_cell_ctrl_t", "lv_table_cell_ctrl_t"),
+        ("lv_table_cell_ctrl_t", "crate::widgets::CellCtrl"),
         ("lv_text_align_t", "lv_text_align_t"),
 
 
@@ -71,6 +134,8 @@ pub trait Rusty {
 pub struct LvWidget {
     name: String,
     methods: Vec<LvFunc>,
+    /// `cfg` gating the widget, e.g. `lv_use_arc`
+    cfg: Option<String>,
 }
 
 impl Rusty for LvWidget {
@@ -84,10 +149,16 @@ impl Rusty for LvWidget {
 
         let widget_name = format_ident!("{}", to_pascal_case(self.name.as_str()));
         let methods: Vec<TokenStream> = self.methods.iter().flat_map(|m| m.code(self)).collect();
+        let cfg = self.cfg.as_ref().map(|cfg| {
+            let cfg = format_ident!("{}", cfg);
+            quote!(#[cfg(#cfg)])
+        });
 
+        // When the widget is configured out, rustc points at the `cfg` for any use of it
         Ok(quote! {
-            define_object!(#widget_name);
+            define_object!(#cfg #widget_name);
 
+            #cfg
             impl<C: 'static> #widget_name<C> {
                 #(#methods)*
             }
@@ -124,6 +195,10 @@ impl Rusty for LvFunc {
         let new_name = self.name.replace(templ.as_str(), "");
         let original_func_name = format_ident!("{}", self.name.as_str());
 
+        if SKIPPED_FUNCTIONS.contains(&self.name.as_str()) {
+            return Err(WrapperError::Skip);
+        }
+
         // generate constructor
         if new_name.as_str().eq("create") {
             return Ok(quote! {
@@ -149,6 +224,32 @@ impl Rusty for LvFunc {
             arg.code(self)?;
         }
 
+        let has_str_args = self.args.iter().skip(1).any(|arg| arg.typ.is_str());
+        if !has_str_args || self.keeps_str() {
+            return Ok(self.method(func_name, false));
+        }
+
+        // Strings are copied by LVGL, so they can come from a temporary buffer. The `&CStr`
+        // variant avoids the copy into that buffer.
+        let cstr_func_name = format_ident!("{}_cstr", new_name);
+        let str_method = self.method(func_name, true);
+        let cstr_method = self.method(cstr_func_name, false);
+        Ok(quote! {
+            #str_method
+            #cstr_method
+        })
+    }
+}
+
+impl LvFunc {
+    /// Whether LVGL keeps pointing to the strings passed to this function
+    fn keeps_str(&self) -> bool {
+        STATIC_STR_FUNCTIONS.contains(&self.name.as_str())
+    }
+
+    /// Generate the method, taking `impl AsRef<str>` rather than `&CStr` for strings if `as_str`
+    fn method(&self, func_name: Ident, as_str: bool) -> TokenStream {
+        let original_func_name = format_ident!("{}", self.name.as_str());
         let is_const = self.args[0].get_type().is_const();
 
         let args_decl = self
@@ -163,6 +264,9 @@ impl Rusty for LvFunc {
                     } else {
                         quote!(&mut self)
                     }
+                } else if as_str && arg.typ.is_str() {
+                    let name = arg.get_name_ident();
+                    quote!(#name: impl AsRef<str>)
                 } else {
                     arg.code(self).unwrap()
                 };
@@ -209,6 +313,10 @@ impl Rusty for LvFunc {
                 // if first arg is `const`, then it should be immutable
                 let next_arg = if i == 0 {
                     quote!(&mut *self.raw)
+                } else if as_str && arg.typ.is_str() {
+                    // Already a pointer to the copy
+                    let var = arg.get_name_ident();
+                    quote!(#var)
                 } else {
                     let var = arg.get_value_usage();
                     quote!(#var)
@@ -230,16 +338,31 @@ impl Rusty for LvFunc {
             quote!(&mut Self)
         };
 
+        let mut call = quote! {
+            unsafe {
+                lvgl_sys::#original_func_name(#args_call);
+            }
+        };
+        if as_str {
+            // Copy each string in turn, the innermost call sees all of the copies
+            for arg in self.args.iter().skip(1).rev().filter(|arg| arg.typ.is_str()) {
+                let name = arg.get_name_ident();
+                call = quote! {
+                    crate::core::with_cstr(#name.as_ref(), |#name| {
+                        #call
+                    });
+                };
+            }
+        }
+
         // TODO: Handle methods that return types
-        Ok(quote! {
+        quote! {
             pub fn #func_name(#args_decl) -> #ret_type {
                 #args_processing
-                unsafe {
-                    lvgl_sys::#original_func_name(#args_call);
-                }
+                #call
                 self
             }
-        })
+        }
     }
 }
 
@@ -334,6 +457,10 @@ impl LvArg {
             quote! {
                 #ident.as_ptr()
             }
+        } else if self.typ.is_rust_type() {
+            quote! {
+                #ident.into()
+            }
         } else {
             quote! {
                 #ident
@@ -349,9 +476,14 @@ impl LvArg {
 impl Rusty for LvArg {
     type Parent = LvFunc;
 
-    fn code(&self, _parent: &Self::Parent) -> WrapperResult<TokenStream> {
+    fn code(&self, parent: &Self::Parent) -> WrapperResult<TokenStream> {
         let name = self.get_name_ident();
         let typ = self.typ.code(self)?;
+        if self.typ.is_str() && parent.keeps_str() {
+            return Ok(quote! {
+                #name: &'static cstr_core::CStr
+            });
+        }
         Ok(quote! {
             #name: #typ
         })
@@ -383,6 +515,13 @@ impl LvType {
     pub fn is_str(&self) -> bool {
         self.literal_name.ends_with("* const cty :: c_char")
     }
+
+    /// Mapped to a type of the `lvgl` crate rather than of `lvgl_sys`
+    pub fn is_rust_type(&self) -> bool {
+        TYPE_MAPPINGS
+            .get(self.literal_name.as_str())
+            .is_some_and(|name| name.contains("::"))
+    }
 }
 
 impl Rusty for LvType {
@@ -393,6 +532,9 @@ impl Rusty for LvType {
             Some(name) => {
                 let val = if self.is_str() {
                     quote!(&cstr_core::CStr)
+                } else if self.is_rust_type() {
+                    let path: syn::Path = syn::parse_str(name).unwrap();
+                    quote!(#path)
                 } else {
                     let ident = format_ident!("{}", name);
                     if name.ends_with("_t") {
@@ -422,28 +564,78 @@ impl From<Box<syn::Type>> for LvType {
 pub struct CodeGen {
     functions: Vec<LvFunc>,
     widgets: Vec<LvWidget>,
+    use_flags: BTreeMap<String, bool>,
+    int_consts: BTreeMap<String, i64>,
+    fonts: Vec<String>,
 }
 
 impl CodeGen {
     pub fn from(code: &str) -> CGResult<Self> {
         let functions = Self::load_func_defs(code)?;
-        let widgets = Self::extract_widgets(&functions)?;
-        Ok(Self { functions, widgets })
+        let use_flags = Self::load_use_flags(code)?;
+        let widgets = Self::extract_widgets(&functions, &use_flags)?;
+        let int_consts = Self::load_int_consts(code)?;
+        let fonts = Self::load_builtin_fonts(code)?;
+        Ok(Self {
+            functions,
+            widgets,
+            use_flags,
+            int_consts,
+            fonts,
+        })
     }
 
     pub fn get_widgets(&self) -> &Vec<LvWidget> {
         &self.widgets
     }
 
-    fn extract_widgets(functions: &[LvFunc]) -> CGResult<Vec<LvWidget>> {
+    /// The `LV_USE_*` options of `lv_conf.h`, keyed by their `cfg` name (e.g. `lv_use_chart`).
+    pub fn get_use_flags(&self) -> &BTreeMap<String, bool> {
+        &self.use_flags
+    }
+
+    /// An integer constant of the bindings, e.g. `LV_COLOR_DEPTH`.
+    pub fn get_int_const(&self, name: &str) -> Option<i64> {
+        self.int_consts.get(name).copied()
+    }
+
+    /// The fonts built into LVGL, e.g. `lv_font_montserrat_14`.
+    pub fn get_builtin_fonts(&self) -> &Vec<String> {
+        &self.fonts
+    }
+
+    fn extract_widgets(
+        functions: &[LvFunc],
+        use_flags: &BTreeMap<String, bool>,
+    ) -> CGResult<Vec<LvWidget>> {
         let widget_names = Self::get_widget_names(functions);
 
+        let widget_cfg = |name: &str| {
+            let option = CONFIGURABLE_WIDGETS
+                .iter()
+                .find(|(widget, _)| *widget == name)
+                .map(|(_, option)| option.to_lowercase())?;
+            use_flags.get(&option).map(|_| option)
+        };
+
         let mut widgets = widget_names.iter().map(|n|
             (n.as_str(), LvWidget {
             name: n.clone(),
             methods: Vec::new(),
+            cfg: widget_cfg(n),
         })).collect::<HashMap<_,_>>();
 
+        // Disabled widgets are still defined, but configured out
+        for (name, option) in CONFIGURABLE_WIDGETS {
+            if use_flags.get(&option.to_lowercase()) == Some(&false) {
+                widgets.entry(*name).or_insert_with(|| LvWidget {
+                    name: name.to_string(),
+                    methods: Vec::new(),
+                    cfg: widget_cfg(name),
+                });
+            }
+        }
+
         for f in functions {
             if !f.is_method() {
                 continue
@@ -524,6 +716,73 @@ impl CodeGen {
         Ok(fns)
     }
 
+    pub fn load_use_flags(bindgen_code: &str) -> CGResult<BTreeMap<String, bool>> {
+        let flags = Self::load_int_consts(bindgen_code)?
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("LV_USE_"))
+            .map(|(name, value)| (name.to_lowercase(), value != 0))
+            .collect();
+        Ok(flags)
+    }
+
+    /// The integer constants, e.g. `LV_COLOR_DEPTH`
+    pub fn load_int_consts(bindgen_code: &str) -> CGResult<BTreeMap<String, i64>> {
+        let ast: syn::File = syn::parse_str(bindgen_code)?;
+        let consts = ast
+            .items
+            .iter()
+            .filter_map(|e| {
+                if let Item::Const(c) = e {
+                    Some(c)
+                } else {
+                    None
+                }
+            })
+            .filter_map(|c| {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(value),
+                    ..
+                }) = c.expr.as_ref()
+                {
+                    let value = value.base10_parse::<i64>().ok()?;
+                    Some((c.ident.to_string(), value))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Ok(consts)
+    }
+
+    pub fn load_builtin_fonts(bindgen_code: &str) -> CGResult<Vec<String>> {
+        let ast: syn::File = syn::parse_str(bindgen_code)?;
+        let fonts = ast
+            .items
+            .iter()
+            .filter_map(|e| {
+                if let Item::ForeignMod(fm) = e {
+                    Some(fm)
+                } else {
+                    None
+                }
+            })
+            .flat_map(|e| {
+                e.items.iter().filter_map(|it| {
+                    if let ForeignItem::Static(s) = it {
+                        Some(s)
+                    } else {
+                        None
+                    }
+                })
+            })
+            .filter(|s| s.mutability.is_none())
+            .filter(|s| s.ty.to_token_stream().to_string() == "lv_font_t")
+            .map(|s| s.ident.to_string())
+            .filter(|name| name.starts_with("lv_font_"))
+            .collect();
+        Ok(fonts)
+    }
+
     pub fn get_function_names(&self) -> CGResult<Vec<String>> {
         Ok(self.functions.iter().map(|f| f.name.clone()).collect())
     }
@@ -547,7 +806,7 @@ mod test {
 
         let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
 
-        let ffn = cg.get(0).unwrap();
+        let ffn = cg.first().unwrap();
         assert_eq!(ffn.name, "lv_obj_get_screen");
         assert_eq!(ffn.args[0].name, "obj");
     }
@@ -602,6 +861,36 @@ mod test {
         assert_eq!(widget_names.len(), 3);
     }
 
+    #[test]
+    fn can_load_int_consts() {
+        let bindgen_code = quote! {
+            pub const LV_COLOR_DEPTH: u32 = 16;
+            pub const LV_COLOR_16_SWAP: u32 = 0;
+            pub const LV_SYMBOL_OK: &[u8; 4usize] = b"\xEF\x80\x8C\0";
+        };
+        let consts = CodeGen::load_int_consts(bindgen_code.to_string().as_str()).unwrap();
+        assert_eq!(consts.get("LV_COLOR_DEPTH"), Some(&16));
+        assert_eq!(consts.get("LV_COLOR_16_SWAP"), Some(&0));
+        assert_eq!(consts.get("LV_SYMBOL_OK"), None);
+    }
+
+    #[test]
+    fn can_load_builtin_fonts() {
+        let bindgen_code = quote! {
+            extern "C" {
+                pub static lv_font_montserrat_14: lv_font_t;
+                pub static lv_font_unscii_8: lv_font_t;
+                pub static mut lv_font_user: lv_font_t;
+                pub static lv_style_const_prop_id_inv: lv_style_prop_t;
+            }
+        };
+        let fonts = CodeGen::load_builtin_fonts(bindgen_code.to_string().as_str()).unwrap();
+        assert_eq!(
+            fonts,
+            vec!["lv_font_montserrat_14", "lv_font_unscii_8"]
+        );
+    }
+
     #[test]
     fn generate_method_wrapper() {
         // pub fn lv_arc_set_bg_end_angle(arc: *mut lv_obj_t, end: u16);
@@ -616,6 +905,7 @@ mod test {
         let arc_widget = LvWidget {
             name: "arc".to_string(),
             methods: vec![],
+            cfg: None,
         };
 
         let code = arc_set_bg_end_angle.code(&arc_widget).unwrap();
@@ -643,25 +933,111 @@ mod test {
         };
         let cg = CodeGen::load_func_defs(bindgen_code.to_string().as_str()).unwrap();
 
-        let label_set_text = cg.get(0).unwrap().clone();
+        let label_set_text = cg.first().unwrap().clone();
         let parent_widget = LvWidget {
             name: "label".to_string(),
             methods: vec![],
+            cfg: None,
         };
 
         let code = label_set_text.code(&parent_widget).unwrap();
         let expected_code = quote! {
+            pub fn set_text(&mut self, text: impl AsRef<str>) -> &mut Self {
+                crate::core::with_cstr(text.as_ref(), |text| {
+                    unsafe {
+                        lvgl_sys::lv_label_set_text(&mut *self.raw, text);
+                    }
+                });
+                self
+            }
 
-            pub fn set_text(&mut self, text: &cstr_core::CStr) -> crate::LvResult<()> {
+            pub fn set_text_cstr(&mut self, text: &cstr_core::CStr) -> &mut Self {
                 unsafe {
-                    lvgl_sys::lv_label_set_text(
-                        self.core.raw().as_mut(),
-                        text.as_ptr()
-                    );
+                    lvgl_sys::lv_label_set_text(&mut *self.raw, text.as_ptr());
                 }
-                Ok(())
+                self
+            }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
+
+    #[test]
+    fn generate_method_wrapper_for_static_str_types_as_argument() {
+        // pub fn lv_label_set_text_static(obj: *mut lv_obj_t, text: *const cty::c_char);
+        let label_set_text_static = LvFunc::new(
+            "lv_label_set_text_static".to_string(),
+            vec![
+                LvArg::new("obj".to_string(), LvType::new("* mut lv_obj_t".to_string())),
+                LvArg::new("text".to_string(), LvType::new("* const cty :: c_char".to_string())),
+            ],
+            None,
+        );
+        let label_widget = LvWidget {
+            name: "label".to_string(),
+            methods: vec![],
+            cfg: None,
+        };
+
+        let code = label_set_text_static.code(&label_widget).unwrap();
+        let expected_code = quote! {
+            pub fn set_text_static(&mut self, text: &'static cstr_core::CStr) -> &mut Self {
+                unsafe {
+                    lvgl_sys::lv_label_set_text_static(&mut *self.raw, text.as_ptr());
+                }
+                self
             }
+        };
+
+        assert_eq!(code.to_string(), expected_code.to_string());
+    }
 
+    #[test]
+    fn skip_hand_written_functions() {
+        // pub fn lv_canvas_fill_bg(canvas: *mut lv_obj_t, color: lv_color_t, opa: lv_opa_t);
+        let canvas_fill_bg = LvFunc::new(
+            "lv_canvas_fill_bg".to_string(),
+            vec![
+                LvArg::new("canvas".to_string(), LvType::new("*mut lv_obj_t".to_string())),
+                LvArg::new("color".to_string(), LvType::new("lv_color_t".to_string())),
+                LvArg::new("opa".to_string(), LvType::new("lv_opa_t".to_string())),
+            ],
+            None,
+        );
+        let canvas_widget = LvWidget {
+            name: "canvas".to_string(),
+            methods: vec![],
+            cfg: None,
+        };
+
+        assert!(canvas_fill_bg.code(&canvas_widget).is_err());
+    }
+
+    #[test]
+    fn generate_method_wrapper_for_rust_types_as_argument() {
+        // pub fn lv_chart_set_type(obj: *mut lv_obj_t, type_: lv_chart_type_t);
+        let chart_set_type = LvFunc::new(
+            "lv_chart_set_type".to_string(),
+            vec![
+                LvArg::new("obj".to_string(), LvType::new("*mut lv_obj_t".to_string())),
+                LvArg::new("type_".to_string(), LvType::new("lv_chart_type_t".to_string())),
+            ],
+            None,
+        );
+        let chart_widget = LvWidget {
+            name: "chart".to_string(),
+            methods: vec![],
+            cfg: None,
+        };
+
+        let code = chart_set_type.code(&chart_widget).unwrap();
+        let expected_code = quote! {
+            pub fn set_type(&mut self, type_: crate::widgets::ChartType) -> &mut Self {
+                unsafe {
+                    lvgl_sys::lv_chart_set_type(&mut *self.raw, type_.into());
+                }
+                self
+            }
         };
 
         assert_eq!(code.to_string(), expected_code.to_string());
@@ -672,13 +1048,15 @@ mod test {
         let arc_widget = LvWidget {
             name: "arc".to_string(),
             methods: vec![],
+            cfg: Some("lv_use_arc".to_string()),
         };
 
         let code = arc_widget.code(&()).unwrap();
         let expected_code = quote! {
-            define_object!(Arc);
+            define_object!(#[cfg(lv_use_arc)] Arc);
 
-            impl Arc {
+            #[cfg(lv_use_arc)]
+            impl<C: 'static> Arc<C> {
 
             }
         };
@@ -686,6 +1064,38 @@ mod test {
         assert_eq!(code.to_string(), expected_code.to_string());
     }
 
+    #[test]
+    fn disabled_widgets_are_configured_out() {
+        let bindgen_code = quote! {
+            pub const LV_USE_ARC: u32 = 1;
+            pub const LV_USE_CHART: u32 = 0;
+            pub const LV_USE_LOG: u32 = 0;
+            extern "C" {
+                pub fn lv_arc_create(parent: *mut lv_obj_t) -> *mut lv_obj_t;
+            }
+        };
+        let cg = CodeGen::from(bindgen_code.to_string().as_str()).unwrap();
+
+        let flags = cg.get_use_flags();
+        assert_eq!(flags.get("lv_use_arc"), Some(&true));
+        assert_eq!(flags.get("lv_use_chart"), Some(&false));
+        assert_eq!(flags.get("lv_use_log"), Some(&false));
+
+        let mut widgets = cg
+            .get_widgets()
+            .iter()
+            .map(|w| (w.name.as_str(), w.cfg.as_deref(), w.methods.len()))
+            .collect::<Vec<_>>();
+        widgets.sort();
+        assert_eq!(
+            widgets,
+            vec![
+                ("arc", Some("lv_use_arc"), 1),
+                ("chart", Some("lv_use_chart"), 0)
+            ]
+        );
+    }
+
     #[test]
     fn generate_widget_with_constructor_code() {
         // pub fn lv_arc_create(par: *mut lv_obj_t, copy: *const lv_obj_t) -> *mut lv_obj_t;
@@ -704,6 +1114,7 @@ mod test {
         let arc_widget = LvWidget {
             name: "arc".to_string(),
             methods: vec![arc_create],
+            cfg: None,
         };
 
         let code = arc_widget.code(&()).unwrap();
@@ -0,0 +1,354 @@
+//! Procedural macros of `lvgl`. Use them through the re-exports of the `lvgl` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span};
+use quote::{format_ident, quote};
+use std::env;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, LitStr, Token};
+
+/// Largest width and height of an image, the header stores them on 11 bits
+const MAX_IMAGE_SIZE: u32 = (1 << 11) - 1;
+
+/// Pure green, the default `LV_COLOR_CHROMA_KEY`
+const CHROMA_KEY: [u8; 4] = [0x00, 0xff, 0x00, 0xff];
+
+/// Converts a PNG into a static LVGL image at compile time, and evaluates to a
+/// `&'static lvgl::image::ImageDsc`.
+///
+/// The path is relative to the root of the crate (`CARGO_MANIFEST_DIR`). The format is one of
+/// `TrueColor` (the default), `TrueColorAlpha`, `TrueColorChromaKeyed`, `Alpha1Bit`, `Alpha2Bit`,
+/// `Alpha4Bit` and `Alpha8Bit`.
+///
+/// ```ignore
+/// static LOGO: &ImageDsc = lvgl::include_image!("assets/logo.png", format = TrueColorAlpha);
+/// ```
+#[proc_macro]
+pub fn include_image(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ImageArgs);
+    match image_code(&args) {
+        Ok(code) => code.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Bundles the files of a directory, and evaluates to a `lvgl::fs::MemFs` serving them.
+///
+/// The path is relative to the root of the crate (`CARGO_MANIFEST_DIR`). Files are embedded
+/// with `include_bytes!`, so changing them triggers a rebuild, but adding new ones doesn't.
+///
+/// ```ignore
+/// let assets = Drive::new(&lvgl, 'A', lvgl::include_assets!("assets"));
+/// let font = Font::load_file(cstr!("A:/fonts/noto_sans_24.bin"));
+/// ```
+#[proc_macro]
+pub fn include_assets(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match assets_code(&path) {
+        Ok(code) => code.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn assets_code(path: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let err = |msg: String| syn::Error::new(path.span(), msg);
+
+    let root = env::var("CARGO_MANIFEST_DIR").map_err(|e| err(e.to_string()))?;
+    let root = PathBuf::from(root).join(path.value());
+    let mut files = Vec::new();
+    collect_files(&root, "", &mut files).map_err(|e| err(format!("{}: {}", root.display(), e)))?;
+
+    let files = files.iter().map(|(name, path)| {
+        let path = path.to_string_lossy();
+        quote!((#name, include_bytes!(#path) as &[u8]))
+    });
+    Ok(quote! {
+        {
+            static FILES: &[(&str, &[u8])] = &[#(#files),*];
+            ::lvgl::fs::MemFs::new(FILES)
+        }
+    })
+}
+
+/// Lists the files under `dir`, recursively and sorted, with their path relative to the root
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    files: &mut Vec<(String, PathBuf)>,
+) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{}/", name), files)?;
+        } else {
+            files.push((name, entry.path()));
+        }
+    }
+    Ok(())
+}
+
+struct ImageArgs {
+    path: LitStr,
+    format: Option<Ident>,
+}
+
+impl Parse for ImageArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut format = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "format" {
+                return Err(syn::Error::new(key.span(), "expected `format = ...`"));
+            }
+            input.parse::<Token![=]>()?;
+            format = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self { path, format })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    TrueColor,
+    TrueColorAlpha,
+    TrueColorChromaKeyed,
+    Alpha(u8),
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "TrueColor" => Format::TrueColor,
+            "TrueColorAlpha" => Format::TrueColorAlpha,
+            "TrueColorChromaKeyed" => Format::TrueColorChromaKeyed,
+            "Alpha1Bit" => Format::Alpha(1),
+            "Alpha2Bit" => Format::Alpha(2),
+            "Alpha4Bit" => Format::Alpha(4),
+            "Alpha8Bit" => Format::Alpha(8),
+            _ => return None,
+        })
+    }
+}
+
+/// The layouts of `lv_color_t`, depending on `LV_COLOR_DEPTH` and `LV_COLOR_16_SWAP`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ColorDepth {
+    One,
+    Eight,
+    Sixteen,
+    SixteenSwapped,
+    ThirtyTwo,
+}
+
+const COLOR_DEPTHS: [ColorDepth; 5] = [
+    ColorDepth::One,
+    ColorDepth::Eight,
+    ColorDepth::Sixteen,
+    ColorDepth::SixteenSwapped,
+    ColorDepth::ThirtyTwo,
+];
+
+fn image_code(args: &ImageArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let err = |msg: String| syn::Error::new(args.path.span(), msg);
+
+    let format_ident = args
+        .format
+        .clone()
+        .unwrap_or_else(|| Ident::new("TrueColor", Span::call_site()));
+    let format = Format::from_name(&format_ident.to_string())
+        .ok_or_else(|| syn::Error::new(format_ident.span(), "unsupported image format"))?;
+
+    let root = env::var("CARGO_MANIFEST_DIR").map_err(|e| err(e.to_string()))?;
+    let path = PathBuf::from(root).join(args.path.value());
+    let (width, height, pixels) =
+        load_png(&path).map_err(|e| err(format!("{}: {}", path.display(), e)))?;
+    if width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+        return Err(err(format!(
+            "{}: LVGL images are at most {}x{} px",
+            path.display(),
+            MAX_IMAGE_SIZE,
+            MAX_IMAGE_SIZE
+        )));
+    }
+
+    let data = match format {
+        Format::Alpha(bpp) => {
+            let data = Literal::byte_string(&encode_alpha(&pixels, width as usize, bpp));
+            quote!(#data)
+        }
+        _ => {
+            // The color depth is only known by lvgl, so all of them are generated
+            let data = COLOR_DEPTHS.iter().map(|depth| {
+                let data = Literal::byte_string(&encode_true_color(&pixels, *depth, format));
+                quote!(#data as &[u8])
+            });
+            quote!(::lvgl::image::__select_color_depth([#(#data),*]))
+        }
+    };
+
+    let path = path.to_string_lossy();
+    let (width, height) = (width as u16, height as u16);
+    let format = format_ident!("{}", format_ident);
+    Ok(quote! {
+        {
+            // Rebuild when the image changes
+            const _: &[u8] = include_bytes!(#path);
+            static IMAGE: ::lvgl::image::ImageDsc = ::lvgl::image::ImageDsc::new(
+                ::lvgl::image::ColorFormat::#format,
+                #width,
+                #height,
+                #data,
+            );
+            &IMAGE
+        }
+    })
+}
+
+type Pixels = Vec<[u8; 4]>;
+
+/// Decodes a PNG into RGBA pixels
+fn load_png(path: &Path) -> Result<(u32, u32, Pixels), Box<dyn std::error::Error>> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    let buf = &buf[..info.buffer_size()];
+
+    let pixels = match info.color_type {
+        png::ColorType::Grayscale => buf.iter().map(|&l| [l, l, l, 0xff]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks(2).map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Rgb => buf.chunks(3).map(|p| [p[0], p[1], p[2], 0xff]).collect(),
+        png::ColorType::Rgba => buf.chunks(4).map(|p| [p[0], p[1], p[2], p[3]]).collect(),
+        png::ColorType::Indexed => return Err("indexed PNGs should have been expanded".into()),
+    };
+    Ok((info.width, info.height, pixels))
+}
+
+/// Encodes pixels as `lv_color_t`, followed by the alpha byte for `TrueColorAlpha`. At 32 bits
+/// the alpha is the 4th byte of the color instead, as `LV_IMG_PX_SIZE_ALPHA_BYTE` is 4.
+fn encode_true_color(pixels: &[[u8; 4]], depth: ColorDepth, format: Format) -> Vec<u8> {
+    let mut data = Vec::new();
+    for &pixel in pixels {
+        let [r, g, b, a] = match format {
+            Format::TrueColorChromaKeyed if pixel[3] < 0x80 => CHROMA_KEY,
+            _ => pixel,
+        };
+        match depth {
+            ColorDepth::One => data.push((r >> 7) | (g >> 7) | (b >> 7)),
+            ColorDepth::Eight => data.push((r & 0xe0) | ((g & 0xe0) >> 3) | (b >> 6)),
+            ColorDepth::Sixteen | ColorDepth::SixteenSwapped => {
+                let c = ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3);
+                if depth == ColorDepth::Sixteen {
+                    data.extend_from_slice(&c.to_le_bytes());
+                } else {
+                    data.extend_from_slice(&c.to_be_bytes());
+                }
+            }
+            ColorDepth::ThirtyTwo if format == Format::TrueColorAlpha => {
+                data.extend_from_slice(&[b, g, r, a])
+            }
+            ColorDepth::ThirtyTwo => data.extend_from_slice(&[b, g, r, 0xff]),
+        }
+        if format == Format::TrueColorAlpha && depth != ColorDepth::ThirtyTwo {
+            data.push(a);
+        }
+    }
+    data
+}
+
+/// Encodes the alpha channel on `bpp` bits per pixel, most significant bits first. Rows start
+/// on a byte boundary.
+fn encode_alpha(pixels: &[[u8; 4]], width: usize, bpp: u8) -> Vec<u8> {
+    let max = (1u32 << bpp) - 1;
+    let mut data = Vec::new();
+    for row in pixels.chunks(width) {
+        let mut byte = 0u8;
+        let mut bits = 0;
+        for pixel in row {
+            let value = ((pixel[3] as u32 * max + 127) / 255) as u8;
+            byte |= value << (8 - bpp - bits);
+            bits += bpp;
+            if bits == 8 {
+                data.push(byte);
+                byte = 0;
+                bits = 0;
+            }
+        }
+        if bits > 0 {
+            data.push(byte);
+        }
+    }
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn collect_files_recursively() {
+        let root = env::temp_dir().join("lvgl_macros_collect_files_recursively");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("fonts")).unwrap();
+        std::fs::write(root.join("fonts/b.bin"), b"b").unwrap();
+        std::fs::write(root.join("a.txt"), b"a").unwrap();
+
+        let mut files = Vec::new();
+        collect_files(&root, "", &mut files).unwrap();
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["a.txt", "fonts/b.bin"]);
+        assert_eq!(files[1].1, root.join("fonts/b.bin"));
+    }
+
+    #[test]
+    fn load_png_as_rgba() {
+        let path = env::temp_dir().join("lvgl_macros_load_png_as_rgba.png");
+        let mut encoder = png::Encoder::new(File::create(&path).unwrap(), 2, 1);
+        encoder.set_color(png::ColorType::GrayscaleAlpha);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0x10, 0xff, 0x20, 0x80]).unwrap();
+        drop(writer);
+
+        let (width, height, pixels) = load_png(&path).unwrap();
+        assert_eq!((width, height), (2, 1));
+        assert_eq!(
+            pixels,
+            vec![[0x10, 0x10, 0x10, 0xff], [0x20, 0x20, 0x20, 0x80]]
+        );
+    }
+
+    #[test]
+    fn encode_true_color_for_all_depths() {
+        let pixels = [[0xff, 0x80, 0x00, 0x40]];
+        let encode = |depth| encode_true_color(&pixels, depth, Format::TrueColorAlpha);
+        assert_eq!(encode(ColorDepth::One), vec![0x01, 0x40]);
+        assert_eq!(encode(ColorDepth::Eight), vec![0b1111_0000, 0x40]);
+        assert_eq!(encode(ColorDepth::Sixteen), vec![0x00, 0xfc, 0x40]);
+        assert_eq!(encode(ColorDepth::SixteenSwapped), vec![0xfc, 0x00, 0x40]);
+        assert_eq!(encode(ColorDepth::ThirtyTwo), vec![0x00, 0x80, 0xff, 0x40]);
+    }
+
+    #[test]
+    fn transparent_pixels_are_chroma_keyed() {
+        let pixels = [[0xff, 0xff, 0xff, 0x00], [0xff, 0xff, 0xff, 0xff]];
+        assert_eq!(
+            encode_true_color(&pixels, ColorDepth::ThirtyTwo, Format::TrueColorChromaKeyed),
+            vec![0x00, 0xff, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+    }
+
+    #[test]
+    fn encode_alpha_pads_rows() {
+        let pixels = [[0, 0, 0, 0xff], [0, 0, 0, 0x00], [0, 0, 0, 0xff]];
+        assert_eq!(encode_alpha(&pixels, 3, 1), vec![0b1010_0000]);
+        assert_eq!(encode_alpha(&pixels, 1, 1), vec![0x80, 0x00, 0x80]);
+        assert_eq!(encode_alpha(&pixels, 3, 4), vec![0xf0, 0xf0]);
+    }
+}
@@ -2,6 +2,7 @@ use cc::Build;
 use std::{env, path::Path, path::PathBuf};
 
 static CONFIG_NAME: &str = "DEP_LV_CONFIG_PATH";
+static CONFIG_TOML_NAME: &str = "DEP_LV_CONFIG_TOML";
 
 fn main() {
     let project_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
@@ -11,47 +12,40 @@ fn main() {
     let vendor = project_dir.join("vendor");
     let vendor_src = vendor.join("lvgl").join("src");
 
-    let lv_config_dir = {
-        let conf_path = env::var(CONFIG_NAME)
-            .map(|raw_path| PathBuf::from(raw_path))
-            .unwrap_or_else(|_| {
-                match std::env::var("DOCS_RS") {
-                    Ok(_) => {
-                        // We've detected that we are building for docs.rs
-                        // so let's use the vendored `lv_conf.h` file.
-                        vendor.join("include")
-                    }
-                    Err(_) => panic!(
-                        "The environment variable {} is required to be defined",
-                        CONFIG_NAME
-                    ),
-                }
-            });
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
 
-        if !conf_path.exists() {
-            panic!(
-                "Directory {} referenced by {} needs to exist",
-                conf_path.to_string_lossy(),
-                CONFIG_NAME
-            );
-        }
-        if !conf_path.is_dir() {
-            panic!("{} needs to be a directory", CONFIG_NAME);
-        }
-        if !conf_path.join("lv_conf.h").exists() {
-            panic!(
-                "Directory {} referenced by {} needs to contain a file called lv_conf.h",
-                conf_path.to_string_lossy(),
-                CONFIG_NAME
+    println!("cargo:rerun-if-env-changed={}", CONFIG_NAME);
+    let lv_config_dir = match env::var(CONFIG_NAME) {
+        Ok(raw_path) => {
+            // A hand-written `lv_conf.h` takes precedence over everything else.
+            let conf_path = PathBuf::from(raw_path);
+            if !conf_path.exists() {
+                panic!(
+                    "Directory {} referenced by {} needs to exist",
+                    conf_path.to_string_lossy(),
+                    CONFIG_NAME
+                );
+            }
+            if !conf_path.is_dir() {
+                panic!("{} needs to be a directory", CONFIG_NAME);
+            }
+            if !conf_path.join("lv_conf.h").exists() {
+                panic!(
+                    "Directory {} referenced by {} needs to contain a file called lv_conf.h",
+                    conf_path.to_string_lossy(),
+                    CONFIG_NAME
+                );
+            }
+
+            println!(
+                "cargo:rerun-if-changed={}",
+                conf_path.join("lv_conf.h").to_str().unwrap()
             );
+            conf_path
         }
-
-        println!(
-            "cargo:rerun-if-changed={}",
-            conf_path.join("lv_conf.h").to_str().unwrap()
-        );
-        conf_path
+        Err(_) => generate_config(&out_path),
     };
+    println!("cargo:config_path={}", lv_config_dir.to_str().unwrap());
 
     let mut cfg = Build::new();
     add_c_files(&mut cfg, &vendor_src);
@@ -60,10 +54,15 @@ fn main() {
     cfg.define("LV_CONF_INCLUDE_SIMPLE", Some("1"))
         .include(&vendor_src)
         .include(&vendor)
+        .include(&shims_dir)
         .warnings(false)
         .include(&lv_config_dir)
-        .archiver("arm-none-eabi-ar") // To avoid "has no symbols" warnings
-        .compile("lvgl");
+        .archiver("arm-none-eabi-ar"); // To avoid "has no symbols" warnings
+
+    // bindgen can't see `static inline` functions and macros, so we generate
+    // C functions wrapping them. The header is what bindgen gets to see.
+    let inline_header = generate_inline_shims(&cfg, &shims_dir, &vendor, &out_path);
+    cfg.file(out_path.join("lvgl_sys_inline.c")).compile("lvgl");
 
     let mut cc_args = vec![
         "-DLV_CONF_INCLUDE_SIMPLE=1",
@@ -71,6 +70,8 @@ fn main() {
         lv_config_dir.to_str().unwrap(),
         "-I",
         vendor.to_str().unwrap(),
+        "-I",
+        shims_dir.to_str().unwrap(),
         "-fvisibility=default",
         "-fshort-enums",
     ];
@@ -123,9 +124,8 @@ fn main() {
 
     eprintln!("include_paths={:?}", include_paths);
 
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
     let bindings = bindgen::Builder::default()
-        .header(shims_dir.join("lvgl_sys.h").to_str().unwrap())
+        .header(inline_header.to_str().unwrap())
         //.rustified_enum("lv_indev_state_t")
         .generate_comments(false)
         .derive_default(true)
@@ -139,9 +139,13 @@ fn main() {
         .generate()
         .expect("Unable to generate bindings");
 
+    let bindings_path = out_path.join("bindings.rs");
     bindings
-        .write_to_file(out_path.join("bindings.rs"))
+        .write_to_file(&bindings_path)
         .expect("Can't write bindings!");
+    // Read by the build script of lvgl as `DEP_LVGL_BINDINGS`, to generate its code from the
+    // configuration of the library that is linked
+    println!("cargo:bindings={}", bindings_path.to_str().unwrap());
 }
 
 fn add_c_files(build: &mut cc::Build, path: impl AsRef<Path>) {
@@ -152,3 +156,380 @@ fn add_c_files(build: &mut cc::Build, path: impl AsRef<Path>) {
         }
     }
 }
+
+/// Options that are turned on by a Cargo feature, and off otherwise.
+static FEATURE_OPTIONS: &[(&str, &str)] = &[
+    ("widget-animimg", "LV_USE_ANIMIMG"),
+    ("widget-arc", "LV_USE_ARC"),
+    ("widget-bar", "LV_USE_BAR"),
+    ("widget-btn", "LV_USE_BTN"),
+    ("widget-btnmatrix", "LV_USE_BTNMATRIX"),
+    ("widget-calendar", "LV_USE_CALENDAR"),
+    ("widget-canvas", "LV_USE_CANVAS"),
+    ("widget-chart", "LV_USE_CHART"),
+    ("widget-checkbox", "LV_USE_CHECKBOX"),
+    ("widget-colorwheel", "LV_USE_COLORWHEEL"),
+    ("widget-dropdown", "LV_USE_DROPDOWN"),
+    ("widget-img", "LV_USE_IMG"),
+    ("widget-imgbtn", "LV_USE_IMGBTN"),
+    ("widget-keyboard", "LV_USE_KEYBOARD"),
+    ("widget-label", "LV_USE_LABEL"),
+    ("widget-led", "LV_USE_LED"),
+    ("widget-line", "LV_USE_LINE"),
+    ("widget-list", "LV_USE_LIST"),
+    ("widget-meter", "LV_USE_METER"),
+    ("widget-msgbox", "LV_USE_MSGBOX"),
+    ("widget-roller", "LV_USE_ROLLER"),
+    ("widget-slider", "LV_USE_SLIDER"),
+    ("widget-span", "LV_USE_SPAN"),
+    ("widget-spinbox", "LV_USE_SPINBOX"),
+    ("widget-spinner", "LV_USE_SPINNER"),
+    ("widget-switch", "LV_USE_SWITCH"),
+    ("widget-table", "LV_USE_TABLE"),
+    ("widget-tabview", "LV_USE_TABVIEW"),
+    ("widget-textarea", "LV_USE_TEXTAREA"),
+    ("widget-tileview", "LV_USE_TILEVIEW"),
+    ("widget-win", "LV_USE_WIN"),
+    ("font-montserrat-12-subpx", "LV_FONT_MONTSERRAT_12_SUBPX"),
+    (
+        "font-montserrat-28-compressed",
+        "LV_FONT_MONTSERRAT_28_COMPRESSED",
+    ),
+    (
+        "font-dejavu-16-persian-hebrew",
+        "LV_FONT_DEJAVU_16_PERSIAN_HEBREW",
+    ),
+    ("font-simsun-16-cjk", "LV_FONT_SIMSUN_16_CJK"),
+    ("font-unscii-8", "LV_FONT_UNSCII_8"),
+    ("font-unscii-16", "LV_FONT_UNSCII_16"),
+    ("theme-default", "LV_USE_THEME_DEFAULT"),
+    ("theme-basic", "LV_USE_THEME_BASIC"),
+    ("theme-mono", "LV_USE_THEME_MONO"),
+    ("flex", "LV_USE_FLEX"),
+    ("grid", "LV_USE_GRID"),
+    ("log", "LV_USE_LOG"),
+    ("snapshot", "LV_USE_SNAPSHOT"),
+];
+
+/// Options on which the former hand-written `lv_conf.h` differed from the defaults of LVGL,
+/// kept so that the generated one renders the same. Features and the TOML file override them.
+static BASE_OPTIONS: &[(&str, &str)] = &[
+    ("LV_MEM_SIZE", "(32U * 1024U)"),
+    ("LV_COLOR_MIX_ROUND_OFS", "(LV_COLOR_DEPTH == 32 ? 0 : 128)"),
+];
+
+/// Sizes of the built-in Montserrat fonts, each behind a `font-montserrat-<size>` feature.
+static MONTSERRAT_SIZES: &[u32] = &[
+    8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30, 32, 34, 36, 38, 40, 42, 44, 46, 48,
+];
+
+/// `color-depth-<bits>` features. At most one of them can be enabled.
+static COLOR_DEPTHS: &[u32] = &[1, 8, 16, 32];
+
+/// `mem-size-<kilobytes>k` features. The largest enabled one wins.
+static MEM_SIZES: &[u32] = &[16, 32, 48, 64, 128, 256];
+
+fn feature_enabled(feature: &str) -> bool {
+    env::var_os(format!(
+        "CARGO_FEATURE_{}",
+        feature.to_uppercase().replace('-', "_")
+    ))
+    .is_some()
+}
+
+/// Writes `lv_conf.h` into `out_path` from the enabled Cargo features, then from the
+/// TOML file referenced by `DEP_LV_CONFIG_TOML`, if any. Everything else is left to the
+/// defaults of `lv_conf_internal.h`.
+fn generate_config(out_path: &Path) -> PathBuf {
+    let mut options: Vec<(String, String)> = Vec::new();
+    let mut set = |name: String, value: String| match options.iter_mut().find(|(n, _)| *n == name) {
+        Some(option) => option.1 = value,
+        None => options.push((name, value)),
+    };
+
+    for (name, value) in BASE_OPTIONS {
+        set(name.to_string(), value.to_string());
+    }
+
+    let depths: Vec<_> = COLOR_DEPTHS
+        .iter()
+        .filter(|depth| feature_enabled(&format!("color-depth-{}", depth)))
+        .collect();
+    match depths.as_slice() {
+        [] => {}
+        [depth] => set("LV_COLOR_DEPTH".into(), depth.to_string()),
+        _ => panic!(
+            "Only one color-depth-* feature can be enabled, found {:?}",
+            depths
+        ),
+    }
+    if let Some(size) = MEM_SIZES
+        .iter()
+        .filter(|size| feature_enabled(&format!("mem-size-{}k", size)))
+        .max()
+    {
+        set("LV_MEM_SIZE".into(), format!("({}U * 1024U)", size));
+    }
+
+    for (feature, name) in FEATURE_OPTIONS {
+        set(
+            name.to_string(),
+            (feature_enabled(feature) as u8).to_string(),
+        );
+    }
+    let fonts: Vec<_> = MONTSERRAT_SIZES
+        .iter()
+        .filter(|size| feature_enabled(&format!("font-montserrat-{}", size)))
+        .collect();
+    for size in MONTSERRAT_SIZES {
+        set(
+            format!("LV_FONT_MONTSERRAT_{}", size),
+            (fonts.contains(&size) as u8).to_string(),
+        );
+    }
+    // LVGL defaults to Montserrat 14, which might not be compiled in.
+    if !fonts.contains(&&14) {
+        if let Some(size) = fonts.first() {
+            set(
+                "LV_FONT_DEFAULT".into(),
+                format!("&lv_font_montserrat_{}", size),
+            );
+        }
+    }
+
+    println!("cargo:rerun-if-env-changed={}", CONFIG_TOML_NAME);
+    if let Ok(toml_path) = env::var(CONFIG_TOML_NAME) {
+        println!("cargo:rerun-if-changed={}", toml_path);
+        let content = std::fs::read_to_string(&toml_path).unwrap_or_else(|e| {
+            panic!(
+                "Can't read {} referenced by {}: {}",
+                toml_path, CONFIG_TOML_NAME, e
+            )
+        });
+        let table: toml::value::Table =
+            toml::from_str(&content).unwrap_or_else(|e| panic!("Can't parse {}: {}", toml_path, e));
+        for (name, value) in toml_options(table) {
+            set(name, value);
+        }
+    }
+
+    let option = |name: &str| {
+        options
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    };
+    if option("LV_FONT_DEFAULT").is_none() && option("LV_FONT_MONTSERRAT_14") != Some("1") {
+        panic!(
+            "LV_FONT_DEFAULT is Montserrat 14, which is not compiled in: enable a \
+             font-montserrat-* feature, or set FONT_DEFAULT in the file referenced by {}",
+            CONFIG_TOML_NAME
+        );
+    }
+
+    let mut header = String::from("/* Generated by lvgl-sys/build.rs, do not edit. */\n");
+    header += "#ifndef LV_CONF_H\n#define LV_CONF_H\n\n#include <stdint.h>\n\n";
+    for (name, value) in &options {
+        header += &format!("#define {} {}\n", name, value);
+    }
+    header += "\n#endif /* LV_CONF_H */\n";
+
+    std::fs::write(out_path.join("lv_conf.h"), header).expect("Can't write lv_conf.h");
+    out_path.to_path_buf()
+}
+
+/// Flattens the TOML configuration into `(define, value)` pairs. Keys are the names of the
+/// `lv_conf.h` defines without the `LV_` prefix, tables are only used for grouping.
+fn toml_options(table: toml::value::Table) -> Vec<(String, String)> {
+    let mut options = Vec::new();
+    for (key, value) in table {
+        let name = format!("LV_{}", key.to_uppercase());
+        let value = match value {
+            toml::Value::Table(table) => {
+                options.extend(toml_options(table));
+                continue;
+            }
+            toml::Value::Boolean(b) => (b as u8).to_string(),
+            toml::Value::Integer(i) => i.to_string(),
+            // Strings are pasted as is, so any C expression can be used
+            toml::Value::String(s) => s,
+            value => panic!(
+                "Unsupported value for {} in {}: {}",
+                key, CONFIG_TOML_NAME, value
+            ),
+        };
+        options.push((name, value));
+    }
+    options
+}
+
+/// C macros that are part of the public API but invisible to bindgen. Each entry is
+/// `(name, return type, parameters, macro invocation)`.
+static MACRO_SHIMS: &[(&str, &str, &str, &str)] = &[
+    (
+        "_LV_COLOR_MAKE",
+        "lv_color_t",
+        "uint8_t r, uint8_t g, uint8_t b",
+        "lv_color_make(r, g, b)",
+    ),
+    (
+        "_LV_COLOR_GET_R",
+        "uint16_t",
+        "lv_color_t color",
+        "LV_COLOR_GET_R(color)",
+    ),
+    (
+        "_LV_COLOR_GET_G",
+        "uint16_t",
+        "lv_color_t color",
+        "LV_COLOR_GET_G(color)",
+    ),
+    (
+        "_LV_COLOR_GET_B",
+        "uint16_t",
+        "lv_color_t color",
+        "LV_COLOR_GET_B(color)",
+    ),
+    (
+        "_LV_COLOR_GET_A",
+        "uint16_t",
+        "lv_color_t color",
+        "LV_COLOR_GET_A(color)",
+    ),
+    ("_LV_PCT", "lv_coord_t", "lv_coord_t x", "LV_PCT(x)"),
+    ("_LV_DPX", "lv_coord_t", "lv_coord_t n", "LV_DPX(n)"),
+];
+
+struct InlineFn {
+    ret: String,
+    name: String,
+    params: String,
+    args: Vec<String>,
+}
+
+/// Writes `lvgl_sys_inline.h` and `lvgl_sys_inline.c` into `out_path`, and returns the
+/// path of the header.
+///
+/// Every `static inline` function of LVGL is renamed with the preprocessor while the
+/// LVGL headers are included, so that a regular function with the original name can be
+/// declared (for bindgen) and defined (for the C compiler) next to it.
+fn generate_inline_shims(cfg: &Build, shims_dir: &Path, vendor: &Path, out_path: &Path) -> PathBuf {
+    let output = cfg
+        .get_compiler()
+        .to_command()
+        .arg("-E")
+        .arg(shims_dir.join("lvgl_sys.h"))
+        .output()
+        .expect("Failed to run the C preprocessor on lvgl_sys.h");
+    if !output.status.success() {
+        panic!(
+            "Failed to preprocess lvgl_sys.h:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let source = lvgl_source(&String::from_utf8_lossy(&output.stdout), vendor);
+    let functions = find_inline_functions(&source);
+
+    let mut header = String::from("/* Generated by lvgl-sys/build.rs, do not edit. */\n");
+    header += "#ifndef LVGL_SYS_INLINE_H\n#define LVGL_SYS_INLINE_H\n\n";
+    for f in &functions {
+        header += &format!("#define {0} {0}__inline\n", f.name);
+    }
+    header += "#include \"lvgl_sys.h\"\n";
+    for f in &functions {
+        header += &format!("#undef {}\n", f.name);
+    }
+    header += "\n";
+    for f in &functions {
+        header += &format!("{} {}({});\n", f.ret, f.name, f.params);
+    }
+    for (name, ret, params, _) in MACRO_SHIMS {
+        header += &format!("{} {}({});\n", ret, name, params);
+    }
+    header += "\n#endif /* LVGL_SYS_INLINE_H */\n";
+
+    let mut source = String::from("/* Generated by lvgl-sys/build.rs, do not edit. */\n");
+    source += "#include \"lvgl_sys_inline.h\"\n";
+    for f in &functions {
+        let ret = if f.ret == "void" { "" } else { "return " };
+        source += &format!(
+            "\n{} {}({})\n{{\n    {}{}__inline({});\n}}\n",
+            f.ret,
+            f.name,
+            f.params,
+            ret,
+            f.name,
+            f.args.join(", ")
+        );
+    }
+    for (name, ret, params, body) in MACRO_SHIMS {
+        source += &format!(
+            "\n{} {}({})\n{{\n    return {};\n}}\n",
+            ret, name, params, body
+        );
+    }
+
+    let header_path = out_path.join("lvgl_sys_inline.h");
+    std::fs::write(&header_path, header).expect("Can't write lvgl_sys_inline.h");
+    std::fs::write(out_path.join("lvgl_sys_inline.c"), source)
+        .expect("Can't write lvgl_sys_inline.c");
+    header_path
+}
+
+/// Keeps only the preprocessed lines coming from the LVGL sources, using the line
+/// markers (`# 42 "path/to/file.h"`) emitted by the preprocessor.
+fn lvgl_source(preprocessed: &str, vendor: &Path) -> String {
+    let lvgl_dir = vendor.join("lvgl");
+    let lvgl_dir = lvgl_dir.to_str().unwrap();
+    let mut in_lvgl = false;
+    let mut source = String::new();
+    for line in preprocessed.lines() {
+        if let Some(marker) = line.strip_prefix("# ") {
+            if let Some(file) = marker.split('"').nth(1) {
+                in_lvgl = file.starts_with(lvgl_dir);
+            }
+        } else if in_lvgl {
+            source += line;
+            source += "\n";
+        }
+    }
+    source
+}
+
+fn find_inline_functions(source: &str) -> Vec<InlineFn> {
+    let fn_re =
+        regex::Regex::new(r"static\s+inline\s+([A-Za-z_][\w\s\*]*?)\b(lv_\w+)\s*\(([^()]*)\)\s*\{")
+            .unwrap();
+    let arg_re = regex::Regex::new(r"(\w+)\s*(\[[^\]]*\])?$").unwrap();
+
+    let mut functions: Vec<InlineFn> = Vec::new();
+    'functions: for cap in fn_re.captures_iter(source) {
+        let name = cap[2].to_string();
+        if functions.iter().any(|f| f.name == name) {
+            continue;
+        }
+        let params = cap[3].split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut args = Vec::new();
+        if params != "void" && !params.is_empty() {
+            for param in params.split(',') {
+                let param = param.trim();
+                match arg_re.captures(param) {
+                    Some(arg) if !param.contains("...") => args.push(arg[1].to_string()),
+                    // Variadic functions can't be forwarded
+                    _ => continue 'functions,
+                }
+            }
+        }
+        functions.push(InlineFn {
+            ret: cap[1].split_whitespace().collect::<Vec<_>>().join(" "),
+            name,
+            params: if params.is_empty() {
+                "void".into()
+            } else {
+                params
+            },
+            args,
+        });
+    }
+    functions
+}
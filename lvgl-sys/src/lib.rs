@@ -8,17 +8,11 @@
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
 pub fn _bindgen_raw_src() -> &'static str {
-    concat!(
-        include_str!(concat!(env!("OUT_DIR"), "/bindings.rs")),
-        include_str!("static_inline.rs"),
-    )
+    include_str!(concat!(env!("OUT_DIR"), "/bindings.rs"))
 }
 
 mod string_impl;
 
-mod static_inline;
-pub use static_inline::*;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,4 +29,23 @@ mod tests {
             assert_eq!(vertical_resolution, 240 as i16);
         }
     }
+
+    #[test]
+    fn static_inline_and_macro_shims() {
+        unsafe {
+            let area = lv_area_t {
+                x1: 10,
+                y1: 10,
+                x2: 29,
+                y2: 19,
+            };
+            assert_eq!(lv_area_get_width(&area), 20);
+            assert_eq!(lv_area_get_height(&area), 10);
+
+            let color = _LV_COLOR_MAKE(0xff, 0x00, 0x00);
+            assert_ne!(_LV_COLOR_GET_R(color), 0);
+            assert_eq!(_LV_COLOR_GET_G(color), 0);
+            assert_eq!(_LV_COLOR_GET_B(color), 0);
+        }
+    }
 }
@@ -1,17 +1,14 @@
 use lvgl_codegen::{CodeGen, Rusty};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 
-fn generate_widgets(out_path: &Path) {
+fn generate_widgets(out_path: &Path, codegen: &CodeGen) {
     let rs = out_path.join("generated-widgets.rs");
 
-    let widgets_impl = lvgl_sys::_bindgen_raw_src();
-
-    let codegen = CodeGen::from(widgets_impl).unwrap();
     let widgets_impl: Vec<TokenStream> = codegen
         .get_widgets()
         .iter()
@@ -31,13 +28,59 @@ fn generate_widgets(out_path: &Path) {
     .unwrap();
 }
 
+/// Exposes the `LV_USE_*` options of `lv_conf.h` as `cfg`s, e.g. `#[cfg(lv_use_chart)]`.
+fn generate_cfg_flags(codegen: &CodeGen) {
+    for (name, enabled) in codegen.get_use_flags() {
+        println!("cargo:rustc-check-cfg=cfg({})", name);
+        if *enabled {
+            println!("cargo:rustc-cfg={}", name);
+        }
+    }
+}
+
+fn generate_fonts(out_path: &Path, codegen: &CodeGen) {
+    let rs = out_path.join("generated-fonts.rs");
+
+    let fonts: Vec<TokenStream> = codegen
+        .get_builtin_fonts()
+        .iter()
+        .map(|font| {
+            let raw = format_ident!("{}", font);
+            let name = format_ident!("{}", font.trim_start_matches("lv_font_").to_uppercase());
+            let doc = format!("`{}`, built into LVGL", font);
+            quote! {
+                #[doc = #doc]
+                pub static #name: &Font = unsafe { Font::from_raw(&lvgl_sys::#raw) };
+            }
+        })
+        .collect();
+
+    let code = quote! {
+        #(#fonts)*
+    };
 
-fn generate_color_settings(out_path: &Path) {
+    let mut file = File::create(rs).unwrap();
+    writeln!(
+        file,
+        "/* automatically generated by lvgl-codegen */\n{}",
+        code
+    )
+    .unwrap();
+}
+
+fn generate_color_settings(out_path: &Path, codegen: &CodeGen) {
     let rs = out_path.join("generated-color-settings.rs");
 
+    let depth = codegen
+        .get_int_const("LV_COLOR_DEPTH")
+        .expect("LV_COLOR_DEPTH is in the bindings");
+    let swap = codegen
+        .get_int_const("LV_COLOR_16_SWAP")
+        .expect("LV_COLOR_16_SWAP is in the bindings");
+
     // We match embedded_graphics precisely what the driver is configured for.
     // This will enable fast drawing.
-    let pixel_color = match (lvgl_sys::LV_COLOR_DEPTH, lvgl_sys::LV_COLOR_16_SWAP) {
+    let pixel_color = match (depth, swap) {
         (1, _) => "BinaryColor",
         (8, _) => panic!("RGB332 is not supported by embedded_graphics_core"),
         (16, 0) => "Rgb565",
@@ -59,6 +102,14 @@ fn generate_color_settings(out_path: &Path) {
 
 fn main() {
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-    generate_widgets(&out_path);
-    generate_color_settings(&out_path);
+    // The bindings of the lvgl-sys we link with. A build dependency on lvgl-sys could be built
+    // with other features, e.g. with the resolver 2.
+    let bindings_path = env::var("DEP_LVGL_BINDINGS").expect("lvgl-sys gives its bindings");
+    println!("cargo:rerun-if-changed={}", bindings_path);
+    let bindings = fs::read_to_string(&bindings_path).unwrap();
+    let codegen = CodeGen::from(&bindings).unwrap();
+    generate_cfg_flags(&codegen);
+    generate_widgets(&out_path, &codegen);
+    generate_fonts(&out_path, &codegen);
+    generate_color_settings(&out_path, &codegen);
 }
@@ -11,6 +11,7 @@ use core::{
 use embedded_graphics_core::{
     prelude::*,
     draw_target::DrawTarget,
+    pixelcolor::Rgb888,
     primitives::Rectangle,
 };
 
@@ -19,6 +20,18 @@ use embedded_graphics_core::{
 // This gives us "pub type PixelColor = embedded_graphics_core::pixel_color::Rgb565;" with the right color
 include!(concat!(env!("OUT_DIR"), "/generated-color-settings.rs"));
 
+/// Converts a color for the functions of `lvgl_sys` taking an `lv_color_t`
+pub fn to_lv_color(color: PixelColor) -> lvgl_sys::lv_color_t {
+    let color = Rgb888::from(color);
+    unsafe { lvgl_sys::lv_color_make(color.r(), color.g(), color.b()) }
+}
+
+/// Converts an `lv_color_t` returned by `lvgl_sys`
+pub fn from_lv_color(color: lvgl_sys::lv_color_t) -> PixelColor {
+    let argb = unsafe { lvgl_sys::lv_color_to32(color) };
+    PixelColor::from(Rgb888::new((argb >> 16) as u8, (argb >> 8) as u8, argb as u8))
+}
+
 /// `Display` represents a display for Lvgl
 /// Limitations:
 /// * No async drawing, no double buffering
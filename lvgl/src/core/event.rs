@@ -1,7 +1,9 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use lvgl_sys::lv_obj_t;
 
+use core::any::Any;
 use core::convert::TryInto;
+use core::ptr;
 
 crate::native_enum! {
     lvgl_sys::lv_event_code_t,
@@ -113,14 +115,25 @@ crate::native_enum! {
 }
 
 
-pub(crate) fn add_event_cb<F>(obj_raw: &mut lv_obj_t, event: Option<Event>, cb: F)
+pub(crate) fn add_event_cb<F>(obj_raw: &mut lv_obj_t, event: Option<Event>, mut cb: F)
 where
     F: FnMut(Event, &mut lv_obj_t, &mut lv_obj_t) + 'static
+{
+    add_event_cb_with_param(obj_raw, event, move |e, current_target, target, _param| {
+        cb(e, current_target, target)
+    });
+}
+
+/// Like `add_event_cb()`, also passing the parameter of the event, e.g. the inserted text of
+/// `Event::Insert`
+pub(crate) fn add_event_cb_with_param<F>(obj_raw: &mut lv_obj_t, event: Option<Event>, cb: F)
+where
+    F: FnMut(Event, &mut lv_obj_t, &mut lv_obj_t, *mut cty::c_void) + 'static
 {
     // XXX FIXME We need to cleanup our Boxing at some point.
     // We use dyn to avoid generating too much code with the event_callback function.
     // But then we need more boxing. It's a little sad.
-    let user_closure: Box<Box<dyn FnMut(_,_,_)>> = Box::new(Box::new(cb));
+    let user_closure: Box<Box<dyn FnMut(_,_,_,_)>> = Box::new(Box::new(cb));
     let user_data = unsafe { core::mem::transmute(Box::into_raw(user_closure)) };
 
     let event = event.map(|e| e.into()).unwrap_or(lvgl_sys::lv_event_code_t_LV_EVENT_ALL);
@@ -135,6 +148,60 @@ where
     }
 }
 
+/// Keeps `data` alive as long as the object, for what LVGL only points to, e.g. a canvas buffer.
+/// It is dropped when the object is deleted.
+pub(crate) fn attach<T: 'static>(obj_raw: &mut lv_obj_t, data: T) -> &'static mut T {
+    let data = Box::into_raw(Box::new(data));
+    add_event_cb(obj_raw, Some(Event::Delete), move |_e, current_target, target| {
+        // Deletions of children can bubble up
+        if ptr::eq(current_target, target) {
+            drop(unsafe { Box::from_raw(data) });
+        }
+    });
+    unsafe { &mut *data }
+}
+
+/// Like `attach()`, but returns the same `T` on every call, created with `Default` on the first.
+/// For what LVGL points to until it is replaced, e.g. the map of a button matrix.
+pub(crate) fn attached<T: Default + 'static>(obj_raw: &mut lv_obj_t) -> &'static mut T {
+    // All the data of an object is kept behind a single Delete callback, found back with its
+    // function pointer
+    let mut all = unsafe {
+        lvgl_sys::lv_obj_get_event_user_data(obj_raw, Some(drop_attached))
+    } as *mut Vec<Box<dyn Any>>;
+    if all.is_null() {
+        all = Box::into_raw(Box::new(Vec::new()));
+        unsafe {
+            lvgl_sys::lv_obj_add_event_cb(
+                obj_raw,
+                Some(drop_attached),
+                Event::Delete.into(),
+                all as *mut cty::c_void,
+            );
+        }
+    }
+    let all = unsafe { &mut *all };
+
+    let data = match all.iter_mut().find(|data| data.is::<T>()) {
+        Some(data) => data,
+        None => {
+            all.push(Box::new(T::default()));
+            all.last_mut().unwrap()
+        }
+    };
+    // Boxed, so the data doesn't move when the vec grows
+    let data: *mut T = data.downcast_mut().unwrap();
+    unsafe { &mut *data }
+}
+
+unsafe extern "C" fn drop_attached(event: *mut lvgl_sys::lv_event_t) {
+    // Deletions of children can bubble up
+    if lvgl_sys::lv_event_get_current_target(event) == lvgl_sys::lv_event_get_target(event) {
+        let all = lvgl_sys::lv_event_get_user_data(event) as *mut Vec<Box<dyn Any>>;
+        drop(Box::from_raw(all));
+    }
+}
+
 unsafe extern "C" fn event_callback(event: *mut lvgl_sys::lv_event_t)
 {
     // Seems a bit silly to use functions to access fields, but that's what the
@@ -144,6 +211,7 @@ unsafe extern "C" fn event_callback(event: *mut lvgl_sys::lv_event_t)
     let current_target = lvgl_sys::lv_event_get_current_target(event);
     let target = lvgl_sys::lv_event_get_target(event);
     let user_data = lvgl_sys::lv_event_get_user_data(event);
+    let param = lvgl_sys::lv_event_get_param(event);
 
     if let Ok(event_code) = event_code.try_into() {
         let current_target = current_target.as_mut().unwrap();
@@ -156,8 +224,8 @@ unsafe extern "C" fn event_callback(event: *mut lvgl_sys::lv_event_t)
         //let user_data = user_data as *mut &mut dyn FnMut(Event, &mut lv_obj_t, &mut lv_obj_t);
         //let user_data = user_data as *mut dyn FnMut(Event, &mut lv_obj_t, &mut lv_obj_t);
 
-        let user_data: *mut *mut dyn FnMut(Event, &mut lv_obj_t, &mut lv_obj_t) = core::mem::transmute(user_data);
+        let user_data: *mut *mut dyn FnMut(Event, &mut lv_obj_t, &mut lv_obj_t, *mut cty::c_void) = core::mem::transmute(user_data);
         let closure = user_data.as_mut().unwrap().as_mut().unwrap();
-        closure(event_code, current_target, target);
+        closure(event_code, current_target, target, param);
     }
 }
@@ -0,0 +1,27 @@
+use super::{Lvgl, ObjExt};
+
+/// Objects that keypads and encoders navigate between, see `InputDevice::set_group()`.
+pub struct Group {
+    // pub so that the user can use lvgl_sys functions directly
+    pub raw: *mut lvgl_sys::lv_group_t,
+}
+
+impl Group {
+    /// The group is never freed, like screens and input devices.
+    pub fn new(_lvgl: &Lvgl) -> Self {
+        let raw = unsafe { lvgl_sys::lv_group_create() };
+        assert!(!raw.is_null(), "OOM");
+        Self { raw }
+    }
+
+    pub fn add_obj<C: 'static>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_add_obj(self.raw, &mut *obj.raw) };
+        self
+    }
+
+    /// Focuses `obj`, which must be in the group
+    pub fn focus_obj<C: 'static>(&mut self, obj: &mut impl ObjExt<C>) -> &mut Self {
+        unsafe { lvgl_sys::lv_group_focus_obj(&mut *obj.raw) };
+        self
+    }
+}
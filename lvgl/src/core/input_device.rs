@@ -1,25 +1,26 @@
 use core::mem::{self, MaybeUninit};
 use alloc::boxed::Box;
-use super::Display;
+use super::{Display, Group};
 
 //////////////////
 // Generic trait
 //////////////////
 
 pub trait InputDeviceState {
-    fn populate_lv_indev_data(&self, data: &mut lvgl_sys::lv_indev_data_t);
+    fn populate_lv_indev_data(&mut self, data: &mut lvgl_sys::lv_indev_data_t);
     fn input_device_type() -> lvgl_sys::lv_indev_type_t;
 }
 
 pub struct InputDevice<S> {
     state: Box<S>,
+    indev: &'static mut lvgl_sys::lv_indev_t,
 }
 
 impl<S: InputDeviceState + Default> InputDevice<S> {
     pub fn new<D>(display: &mut Display<D>) -> Self {
         let mut state = Box::new(S::default());
 
-        unsafe {
+        let indev = unsafe {
             let mut indev_drv = {
                 let mut indev_drv = MaybeUninit::<lvgl_sys::lv_indev_drv_t>::uninit();
                 lvgl_sys::lv_indev_drv_init(indev_drv.as_mut_ptr());
@@ -30,13 +31,14 @@ impl<S: InputDeviceState + Default> InputDevice<S> {
                 indev_drv.user_data = mem::transmute(state.as_mut());
                 indev_drv
             };
-            let _indev = lvgl_sys::lv_indev_drv_register(indev_drv.as_mut());
+            let indev = lvgl_sys::lv_indev_drv_register(indev_drv.as_mut());
 
             // lvgl needs indev_drv to stick around
             Box::into_raw(indev_drv);
-        }
+            indev.as_mut().unwrap()
+        };
 
-        Self { state }
+        Self { state, indev }
     }
 
     // We could add a feature to run a user-provided closure when lvgl polls the
@@ -58,6 +60,12 @@ impl<S: InputDeviceState + Default> InputDevice<S> {
     pub fn state(&mut self) -> &mut S {
         self.state.as_mut()
     }
+
+    /// Keypads and encoders only interact with the objects of their group
+    pub fn set_group(&mut self, group: &Group) -> &mut Self {
+        unsafe { lvgl_sys::lv_indev_set_group(&mut *self.indev, group.raw) };
+        self
+    }
 }
 
 impl<S> Drop for InputDevice<S> {
@@ -89,7 +97,7 @@ impl Default for TouchPad {
 }
 
 impl InputDeviceState for TouchPad {
-    fn populate_lv_indev_data(&self, data: &mut lvgl_sys::lv_indev_data_t) {
+    fn populate_lv_indev_data(&mut self, data: &mut lvgl_sys::lv_indev_data_t) {
         match &self {
             TouchPad::Pressed { x, y } => {
                 data.point.x = *x;
@@ -106,3 +114,77 @@ impl InputDeviceState for TouchPad {
         lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_POINTER
     }
 }
+
+
+//////////////////
+// Keypad
+//////////////////
+
+crate::native_enum! {
+    u32,
+    /// Special keys, to navigate in groups and widgets. Other keys are characters.
+    pub enum Key {
+        Up = lvgl_sys::LV_KEY_UP,
+        Down = lvgl_sys::LV_KEY_DOWN,
+        Right = lvgl_sys::LV_KEY_RIGHT,
+        Left = lvgl_sys::LV_KEY_LEFT,
+        Esc = lvgl_sys::LV_KEY_ESC,
+        Del = lvgl_sys::LV_KEY_DEL,
+        Backspace = lvgl_sys::LV_KEY_BACKSPACE,
+        Enter = lvgl_sys::LV_KEY_ENTER,
+        /// Focus the next object of the group
+        Next = lvgl_sys::LV_KEY_NEXT,
+        /// Focus the previous object of the group
+        Prev = lvgl_sys::LV_KEY_PREV,
+        Home = lvgl_sys::LV_KEY_HOME,
+        End = lvgl_sys::LV_KEY_END,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Keypad {
+    /// A `Key`, or a character
+    pub key: u32,
+    pub pressed: bool,
+}
+
+impl InputDeviceState for Keypad {
+    fn populate_lv_indev_data(&mut self, data: &mut lvgl_sys::lv_indev_data_t) {
+        data.key = self.key;
+        data.state = if self.pressed {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
+        } else {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
+        };
+    }
+
+    fn input_device_type() -> lvgl_sys::lv_indev_type_t {
+        lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_KEYPAD
+    }
+}
+
+//////////////////
+// Encoder
+//////////////////
+
+#[derive(Debug, Default)]
+pub struct Encoder {
+    /// Steps turned since the last read, negative to the left. Reset when LVGL reads it.
+    pub diff: i16,
+    pub pressed: bool,
+}
+
+impl InputDeviceState for Encoder {
+    fn populate_lv_indev_data(&mut self, data: &mut lvgl_sys::lv_indev_data_t) {
+        data.enc_diff = mem::take(&mut self.diff);
+        data.state = if self.pressed {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_PRESSED
+        } else {
+            lvgl_sys::lv_indev_state_t_LV_INDEV_STATE_RELEASED
+        };
+    }
+
+    fn input_device_type() -> lvgl_sys::lv_indev_type_t {
+        lvgl_sys::lv_indev_type_t_LV_INDEV_TYPE_ENCODER
+    }
+}
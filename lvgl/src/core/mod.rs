@@ -14,5 +14,11 @@ pub use display::*;
 mod input_device;
 pub use input_device::*;
 
+mod group;
+pub use group::*;
+
 mod screen;
 pub use screen::*;
+
+mod text;
+pub(crate) use text::*;
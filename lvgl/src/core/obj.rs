@@ -5,8 +5,12 @@ use core::{
     ptr,
 };
 
+#[cfg(lv_use_snapshot)]
+use crate::image::{ColorFormat, Snapshot, SnapshotRef};
 use crate::{
-    style::{Align, Flag, State},
+    font::Font,
+    image::ImageSource,
+    style::{Align, Flag, Part, State},
     core::Event,
     core::event::add_event_cb,
 };
@@ -122,16 +126,61 @@ pub trait ObjExt<C: 'static>: Deref<Target = Obj<C>> + DerefMut + Sized {
     fn has_state(&self, state: State) -> bool {
         unsafe { lvgl_sys::lv_obj_has_state(&*self.raw, state.bits()) }
     }
+
+    /// Set the font of the text for a part of the object, in the given state
+    fn set_style_text_font(&mut self, font: &'static Font, part: Part, state: State) -> &mut Self {
+        let part: lvgl_sys::lv_part_t = part.into();
+        let selector = part | state.bits() as lvgl_sys::lv_style_selector_t;
+        unsafe { lvgl_sys::lv_obj_set_style_text_font(&mut *self.raw, font.raw(), selector) };
+        self
+    }
+
+    /// Set the background image for a part of the object, in the given state
+    fn set_style_bg_img_src(
+        &mut self,
+        src: ImageSource<'static>,
+        part: Part,
+        state: State,
+    ) -> &mut Self {
+        let part: lvgl_sys::lv_part_t = part.into();
+        let selector = part | state.bits() as lvgl_sys::lv_style_selector_t;
+        unsafe { lvgl_sys::lv_obj_set_style_bg_img_src(&mut *self.raw, src.as_ptr(), selector) };
+        self
+    }
+
+    /// Render the object and its children into a new image, allocated by LVGL.
+    /// Returns `None` if LVGL is out of memory.
+    #[cfg(lv_use_snapshot)]
+    fn snapshot(&mut self, cf: ColorFormat) -> Option<Snapshot> {
+        Snapshot::take(self.raw, cf)
+    }
+
+    /// The size of the buffer needed by `snapshot_to_buf()`
+    #[cfg(lv_use_snapshot)]
+    fn snapshot_buf_size(&mut self, cf: ColorFormat) -> usize {
+        unsafe { lvgl_sys::lv_snapshot_buf_size_needed(&mut *self.raw, cf.into()) as usize }
+    }
+
+    /// Render the object and its children into `buf`, to reuse it between snapshots.
+    /// Returns `None` if `buf` is too small.
+    #[cfg(lv_use_snapshot)]
+    fn snapshot_to_buf<'a>(&mut self, cf: ColorFormat, buf: &'a mut [u8])
+        -> Option<SnapshotRef<'a>>
+    {
+        SnapshotRef::take(self.raw, cf, buf)
+    }
 }
 
 impl<C: 'static, T: Deref<Target = Obj<C>> + DerefMut + Sized> ObjExt<C> for T {}
 
 macro_rules! define_object {
-    ($item:ident) => {
+    ($(#[$attr:meta])* $item:ident) => {
+        $(#[$attr])*
         pub struct $item<C> {
             pub(crate) obj: Obj<C>
         }
 
+        $(#[$attr])*
         impl<C> core::ops::Deref for $item<C> {
             type Target = Obj<C>;
 
@@ -140,6 +189,7 @@ macro_rules! define_object {
             }
         }
 
+        $(#[$attr])*
         impl<C> core::ops::DerefMut for $item<C> {
             fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.obj
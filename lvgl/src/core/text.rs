@@ -0,0 +1,44 @@
+use alloc::string::String;
+use cstr_core::{CStr, CString};
+use cty::c_char;
+
+/// Texts shorter than this are copied on the stack rather than allocated
+const SCRATCH_LEN: usize = 64;
+
+/// Call `f` with a nul terminated copy of `text`, for LVGL functions that copy it in turn.
+///
+/// `text` is cut at its first nul byte, if any, as LVGL would read it.
+pub(crate) fn with_cstr<R>(text: &str, f: impl FnOnce(*const c_char) -> R) -> R {
+    let bytes = text.as_bytes();
+    let bytes = &bytes[..bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len())];
+    if bytes.len() < SCRATCH_LEN {
+        let mut scratch = [0u8; SCRATCH_LEN];
+        scratch[..bytes.len()].copy_from_slice(bytes);
+        f(scratch.as_ptr() as *const c_char)
+    } else {
+        let text = CString::new(bytes).unwrap();
+        f(text.as_ptr())
+    }
+}
+
+/// A text from LVGL, empty if it is missing or not UTF-8
+pub(crate) fn to_str<'a>(text: *const c_char) -> &'a str {
+    if text.is_null() {
+        return "";
+    }
+    unsafe { CStr::from_ptr(text) }.to_str().unwrap_or_default()
+}
+
+/// Join `lines` with `'\n'`, as LVGL wants lists of options
+pub(crate) fn join_lines<T: AsRef<str>>(lines: impl IntoIterator<Item = T>) -> String {
+    let mut text = String::new();
+    for (i, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        assert!(!line.contains('\n'), "Lines can't contain \"\\n\"");
+        if i > 0 {
+            text.push('\n');
+        }
+        text.push_str(line);
+    }
+    text
+}
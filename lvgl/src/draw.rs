@@ -0,0 +1,259 @@
+//! Descriptors of how to draw rectangles, texts, lines, arcs and images, see `widgets::Canvas`.
+
+use core::mem::MaybeUninit;
+use lvgl_sys::lv_coord_t;
+
+use crate::core::{to_lv_color, PixelColor};
+use crate::font::Font;
+use crate::style::Opacity;
+
+macro_rules! draw_dsc {
+    ($(#[$attr:meta])* $name:ident, $raw:ident, $init:ident) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name {
+            raw: lvgl_sys::$raw,
+        }
+
+        impl $name {
+            pub fn raw(&self) -> &lvgl_sys::$raw {
+                &self.raw
+            }
+
+            /// For the fields without a setter
+            pub fn raw_mut(&mut self) -> &mut lvgl_sys::$raw {
+                &mut self.raw
+            }
+        }
+
+        /// The defaults of LVGL
+        impl Default for $name {
+            fn default() -> Self {
+                let raw = unsafe {
+                    let mut raw = MaybeUninit::uninit();
+                    lvgl_sys::$init(raw.as_mut_ptr());
+                    raw.assume_init()
+                };
+                Self { raw }
+            }
+        }
+    };
+}
+
+draw_dsc!(
+    /// How to draw a rectangle or a polygon
+    RectDsc,
+    lv_draw_rect_dsc_t,
+    lv_draw_rect_dsc_init
+);
+
+impl RectDsc {
+    pub fn set_radius(&mut self, radius: lv_coord_t) -> &mut Self {
+        self.raw.radius = radius;
+        self
+    }
+
+    pub fn set_bg_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.bg_color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_bg_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.bg_opa = opa.into();
+        self
+    }
+
+    pub fn set_border_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.border_color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_border_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.border_width = width;
+        self
+    }
+
+    pub fn set_border_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.border_opa = opa.into();
+        self
+    }
+
+    pub fn set_outline_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.outline_color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_outline_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.outline_width = width;
+        self
+    }
+
+    pub fn set_outline_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.outline_opa = opa.into();
+        self
+    }
+
+    pub fn set_shadow_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.shadow_color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_shadow_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.shadow_width = width;
+        self
+    }
+
+    pub fn set_shadow_ofs(&mut self, x: lv_coord_t, y: lv_coord_t) -> &mut Self {
+        self.raw.shadow_ofs_x = x;
+        self.raw.shadow_ofs_y = y;
+        self
+    }
+
+    pub fn set_shadow_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.shadow_opa = opa.into();
+        self
+    }
+}
+
+draw_dsc!(
+    /// How to draw a text
+    LabelDsc,
+    lv_draw_label_dsc_t,
+    lv_draw_label_dsc_init
+);
+
+impl LabelDsc {
+    pub fn set_font(&mut self, font: &'static Font) -> &mut Self {
+        self.raw.font = font.raw();
+        self
+    }
+
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.opa = opa.into();
+        self
+    }
+
+    pub fn set_letter_space(&mut self, space: lv_coord_t) -> &mut Self {
+        self.raw.letter_space = space;
+        self
+    }
+
+    pub fn set_line_space(&mut self, space: lv_coord_t) -> &mut Self {
+        self.raw.line_space = space;
+        self
+    }
+}
+
+draw_dsc!(
+    /// How to draw a line
+    LineDsc,
+    lv_draw_line_dsc_t,
+    lv_draw_line_dsc_init
+);
+
+impl LineDsc {
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.width = width;
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.opa = opa.into();
+        self
+    }
+
+    /// Dashes of `width` pixels, separated by `gap` pixels. Only for horizontal and vertical lines.
+    pub fn set_dash(&mut self, width: lv_coord_t, gap: lv_coord_t) -> &mut Self {
+        self.raw.dash_width = width;
+        self.raw.dash_gap = gap;
+        self
+    }
+
+    pub fn set_rounded(&mut self, rounded: bool) -> &mut Self {
+        self.raw.set_round_start(rounded as u8);
+        self.raw.set_round_end(rounded as u8);
+        self
+    }
+}
+
+draw_dsc!(
+    /// How to draw an arc
+    ArcDsc,
+    lv_draw_arc_dsc_t,
+    lv_draw_arc_dsc_init
+);
+
+impl ArcDsc {
+    pub fn set_color(&mut self, color: PixelColor) -> &mut Self {
+        self.raw.color = to_lv_color(color);
+        self
+    }
+
+    pub fn set_width(&mut self, width: lv_coord_t) -> &mut Self {
+        self.raw.width = width;
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.opa = opa.into();
+        self
+    }
+
+    pub fn set_rounded(&mut self, rounded: bool) -> &mut Self {
+        self.raw.set_rounded(rounded as u8);
+        self
+    }
+}
+
+draw_dsc!(
+    /// How to draw an image
+    ImgDsc,
+    lv_draw_img_dsc_t,
+    lv_draw_img_dsc_init
+);
+
+impl ImgDsc {
+    /// In 0.1 degrees
+    pub fn set_angle(&mut self, angle: i16) -> &mut Self {
+        self.raw.angle = angle;
+        self
+    }
+
+    /// 256 is the original size
+    pub fn set_zoom(&mut self, zoom: u16) -> &mut Self {
+        self.raw.zoom = zoom;
+        self
+    }
+
+    /// Rotate and zoom around this point of the image
+    pub fn set_pivot(&mut self, x: lv_coord_t, y: lv_coord_t) -> &mut Self {
+        self.raw.pivot = lvgl_sys::lv_point_t { x, y };
+        self
+    }
+
+    pub fn set_opa(&mut self, opa: Opacity) -> &mut Self {
+        self.raw.opa = opa.into();
+        self
+    }
+
+    pub fn set_recolor(&mut self, color: PixelColor, opa: Opacity) -> &mut Self {
+        self.raw.recolor = to_lv_color(color);
+        self.raw.recolor_opa = opa.into();
+        self
+    }
+
+    pub fn set_antialias(&mut self, antialias: bool) -> &mut Self {
+        self.raw.set_antialias(antialias as u8);
+        self
+    }
+}
@@ -0,0 +1,279 @@
+use alloc::{boxed::Box, format, vec::Vec};
+use core::{
+    mem,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+use cstr_core::{CStr, CString};
+use lvgl_sys::{lv_coord_t, lv_font_t};
+
+use crate::fs::{self, FileSystem, FsError, FsResult, MemFile, OpenMode, SeekFrom};
+
+/// A font usable by LVGL.
+///
+/// Fonts are always `'static`: LVGL keeps pointers to them in styles and objects,
+/// and never tells us when it's done with them.
+#[repr(transparent)]
+pub struct Font {
+    raw: lv_font_t,
+}
+
+// Fonts are never mutated once created.
+unsafe impl Sync for Font {}
+
+impl Font {
+    /// Wraps a native LVGL font.
+    ///
+    /// # Safety
+    /// The font must be properly initialized, e.g. a font converted to C with
+    /// `lv_font_conv`. See also `font_declare!`.
+    pub const unsafe fn from_raw(raw: &'static lv_font_t) -> &'static Font {
+        mem::transmute(raw)
+    }
+
+    /// Loads a font in the LVGL binary format (`lv_font_conv --format bin`).
+    ///
+    /// LVGL copies what it needs, so `data` can be dropped afterwards. The font itself
+    /// is leaked. Returns `None` if the data is not a valid font.
+    pub fn load(data: &[u8]) -> Option<&'static Font> {
+        crate::core::ensure_init();
+        register_mem_fs();
+
+        let id = NEXT_LOAD_ID.fetch_add(1, Ordering::Relaxed);
+        let mut loading = Loading {
+            id,
+            data: data.as_ptr(),
+            len: data.len(),
+        };
+        LOADING.store(&mut loading, Ordering::Release);
+        let path = CString::new(format!("{}:{:x}", MEM_FS_LETTER as char, id)).unwrap();
+        let font = Self::load_file(&path);
+        LOADING.store(ptr::null_mut(), Ordering::Release);
+        font
+    }
+
+    /// Loads a font in the LVGL binary format from a registered drive, e.g.
+    /// `S:/fonts/noto_sans_24.bin`. See `fs::Drive`.
+    pub fn load_file(path: &CStr) -> Option<&'static Font> {
+        crate::core::ensure_init();
+        let raw = unsafe { lvgl_sys::lv_font_load(path.as_ptr()) };
+        unsafe { raw.as_ref().map(|raw| Self::from_raw(raw)) }
+    }
+
+    pub fn raw(&self) -> &lv_font_t {
+        &self.raw
+    }
+
+    /// The real line height where any text fits
+    pub fn line_height(&self) -> lv_coord_t {
+        self.raw.line_height
+    }
+
+    /// Base line measured from the top of the line height
+    pub fn base_line(&self) -> lv_coord_t {
+        self.raw.base_line
+    }
+}
+
+/// Declares a font converted to C with `lv_font_conv` and linked with the application.
+///
+/// ```ignore
+/// lvgl::font_declare! {
+///     pub NOTO_SANS_NUMERIC_80 = noto_sans_numeric_80;
+/// }
+///
+/// style.set_text_font(NOTO_SANS_NUMERIC_80);
+/// ```
+#[macro_export]
+macro_rules! font_declare {
+    ($($(#[$attr:meta])* $vis:vis $name:ident = $symbol:ident;)*) => {
+        $(
+            $(#[$attr])*
+            $vis static $name: &'static $crate::font::Font = {
+                extern "C" {
+                    static $symbol: $crate::sys::lv_font_t;
+                }
+                unsafe { $crate::font::Font::from_raw(&$symbol) }
+            };
+        )*
+    };
+}
+
+/// Metrics of a `FontData`
+pub struct FontMetrics {
+    pub line_height: lv_coord_t,
+    pub base_line: lv_coord_t,
+    pub underline_position: i8,
+    pub underline_thickness: i8,
+    /// Bits per pixel of the bitmap: 1, 2, 4 or 8
+    pub bpp: u8,
+}
+
+/// A glyph of a `FontData`, see `lv_font_fmt_txt_glyph_dsc_t`
+pub struct GlyphData {
+    /// Start of the glyph in the bitmap
+    pub bitmap_index: u32,
+    /// Advance width, in 1/16 px
+    pub adv_w: u16,
+    pub box_w: u8,
+    pub box_h: u8,
+    pub ofs_x: i8,
+    pub ofs_y: i8,
+}
+
+/// Consecutive code points, starting at `start`, mapped to consecutive glyphs
+pub struct GlyphRange {
+    pub start: u32,
+    pub len: u16,
+    pub glyph_id_start: u16,
+}
+
+/// A bitmap font stored as plain Rust data, as generated by the font converter of
+/// `lvgl-codegen` (`font` feature).
+///
+/// The native font is built on first use and then kept forever. Only the default
+/// `LV_FONT_FMT_TXT_LARGE 0` layout of LVGL is supported.
+pub struct FontData {
+    metrics: FontMetrics,
+    bitmap: &'static [u8],
+    glyphs: &'static [GlyphData],
+    ranges: &'static [GlyphRange],
+    font: AtomicPtr<Font>,
+}
+
+impl FontData {
+    /// The first glyph is reserved and never drawn.
+    pub const fn new(
+        metrics: FontMetrics,
+        bitmap: &'static [u8],
+        glyphs: &'static [GlyphData],
+        ranges: &'static [GlyphRange],
+    ) -> Self {
+        Self {
+            metrics,
+            bitmap,
+            glyphs,
+            ranges,
+            font: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn font(&'static self) -> &'static Font {
+        let font = self.font.load(Ordering::Acquire);
+        if let Some(font) = unsafe { font.as_ref() } {
+            return font;
+        }
+
+        let font = Box::leak(Box::new(self.build())) as *mut Font;
+        match self
+            .font
+            .compare_exchange(ptr::null_mut(), font, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => unsafe { &*font },
+            // Another thread was faster, leaking a font is not the end of the world
+            Err(existing) => unsafe { &*existing },
+        }
+    }
+
+    fn build(&'static self) -> Font {
+        let glyphs: Vec<_> = self
+            .glyphs
+            .iter()
+            .map(|g| {
+                let mut glyph = lvgl_sys::lv_font_fmt_txt_glyph_dsc_t {
+                    box_w: g.box_w,
+                    box_h: g.box_h,
+                    ofs_x: g.ofs_x,
+                    ofs_y: g.ofs_y,
+                    ..Default::default()
+                };
+                glyph.set_bitmap_index(g.bitmap_index);
+                glyph.set_adv_w(g.adv_w as u32);
+                glyph
+            })
+            .collect();
+        let cmaps: Vec<_> = self
+            .ranges
+            .iter()
+            .map(|r| lvgl_sys::lv_font_fmt_txt_cmap_t {
+                range_start: r.start,
+                range_length: r.len,
+                glyph_id_start: r.glyph_id_start,
+                type_: lvgl_sys::LV_FONT_FMT_TXT_CMAP_FORMAT0_TINY
+                    as lvgl_sys::lv_font_fmt_txt_cmap_type_t,
+                ..Default::default()
+            })
+            .collect();
+
+        let mut dsc = lvgl_sys::lv_font_fmt_txt_dsc_t {
+            glyph_bitmap: self.bitmap.as_ptr(),
+            glyph_dsc: Box::leak(glyphs.into_boxed_slice()).as_ptr(),
+            cmaps: Box::leak(cmaps.into_boxed_slice()).as_ptr(),
+            cache: Box::leak(Box::new(Default::default())),
+            ..Default::default()
+        };
+        dsc.set_cmap_num(self.ranges.len() as u16);
+        dsc.set_bpp(self.metrics.bpp as u16);
+        dsc.set_bitmap_format(lvgl_sys::LV_FONT_FMT_TXT_PLAIN as u16);
+
+        Font {
+            raw: lv_font_t {
+                get_glyph_dsc: Some(lvgl_sys::lv_font_get_glyph_dsc_fmt_txt),
+                get_glyph_bitmap: Some(lvgl_sys::lv_font_get_bitmap_fmt_txt),
+                line_height: self.metrics.line_height,
+                base_line: self.metrics.base_line,
+                underline_position: self.metrics.underline_position,
+                underline_thickness: self.metrics.underline_thickness,
+                dsc: Box::leak(Box::new(dsc)) as *const _ as *const cty::c_void,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+// Built-in fonts enabled in lv_conf.h, e.g. `MONTSERRAT_14`
+include!(concat!(env!("OUT_DIR"), "/generated-fonts.rs"));
+
+// lv_font_load() only reads from the file system. So the data given to Font::load() is
+// exposed with a tiny read-only file system, the path being the id of the load. Any other
+// path, e.g. of a former load, is not found. The letter is not printable to stay out of
+// the way of the application drives.
+const MEM_FS_LETTER: u8 = 0x01;
+
+// The data of the font being loaded, only set during Font::load()
+struct Loading {
+    id: usize,
+    data: *const u8,
+    len: usize,
+}
+
+static LOADING: AtomicPtr<Loading> = AtomicPtr::new(ptr::null_mut());
+static NEXT_LOAD_ID: AtomicUsize = AtomicUsize::new(0);
+
+struct MemoryFs;
+
+fn register_mem_fs() {
+    static REGISTERED: AtomicBool = AtomicBool::new(false);
+    if !REGISTERED.fetch_or(true, Ordering::Relaxed) {
+        unsafe { fs::register(MEM_FS_LETTER as char, Box::leak(Box::new(MemoryFs))) };
+    }
+}
+
+impl FileSystem for MemoryFs {
+    type File = MemFile;
+    type Dir = ();
+
+    fn open(&mut self, path: &str, _mode: OpenMode) -> FsResult<MemFile> {
+        let id = usize::from_str_radix(path, 16).map_err(|_| FsError::NotFound)?;
+        match unsafe { LOADING.load(Ordering::Acquire).as_ref() } {
+            // Font::load() keeps the data alive while LVGL reads it, and the file is closed
+            // before lv_font_load() returns
+            Some(loading) if loading.id == id => Ok(MemFile::new(unsafe {
+                core::slice::from_raw_parts(loading.data, loading.len)
+            })),
+            _ => Err(FsError::NotFound),
+        }
+    }
+
+    fs::mem_file_ops!();
+}
@@ -0,0 +1,510 @@
+//! File systems for LVGL, to load images and fonts from paths like `S:/images/logo.bin`.
+//!
+//! Implement `FileSystem` and register it under a drive letter with `Drive::new()`.
+//! `MemFs` serves files bundled with `include_assets!`. With the `std` feature, `StdFs`
+//! exposes a directory of the host.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::String,
+    vec::{self, Vec},
+};
+use core::{marker::PhantomData, mem, ptr, slice};
+use cstr_core::CStr;
+use lvgl_sys::{lv_fs_drv_t, lv_fs_res_t};
+
+use crate::core::Lvgl;
+
+crate::native_enum! {
+    lv_fs_res_t,
+    /// Errors of file system operations, as understood by LVGL
+    pub enum FsError {
+        /// Low level hardware error
+        HardwareError = lvgl_sys::LV_FS_RES_HW_ERR,
+        /// Error in the file system structure
+        Corrupted = lvgl_sys::LV_FS_RES_FS_ERR,
+        /// The file or directory doesn't exist
+        NotFound = lvgl_sys::LV_FS_RES_NOT_EX,
+        /// Disk full
+        Full = lvgl_sys::LV_FS_RES_FULL,
+        /// The file is already opened
+        Locked = lvgl_sys::LV_FS_RES_LOCKED,
+        /// Access denied
+        Denied = lvgl_sys::LV_FS_RES_DENIED,
+        /// The file system can't handle it now, try later
+        Busy = lvgl_sys::LV_FS_RES_BUSY,
+        Timeout = lvgl_sys::LV_FS_RES_TOUT,
+        NotImplemented = lvgl_sys::LV_FS_RES_NOT_IMP,
+        OutOfMemory = lvgl_sys::LV_FS_RES_OUT_OF_MEM,
+        InvalidParam = lvgl_sys::LV_FS_RES_INV_PARAM,
+        Unknown = lvgl_sys::LV_FS_RES_UNKNOWN,
+    }
+}
+
+pub type FsResult<T> = Result<T, FsError>;
+
+bitflags! {
+    pub struct OpenMode: lvgl_sys::lv_fs_mode_t {
+        const WRITE = lvgl_sys::LV_FS_MODE_WR as lvgl_sys::lv_fs_mode_t;
+        const READ = lvgl_sys::LV_FS_MODE_RD as lvgl_sys::lv_fs_mode_t;
+    }
+}
+
+/// Like `std::io::SeekFrom`, with the unsigned offsets of LVGL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeekFrom {
+    Start(u32),
+    Current(u32),
+    End(u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// A file system that LVGL can read from, and optionally write to.
+///
+/// Paths are relative to the drive, e.g. `images/logo.bin` for `S:/images/logo.bin`.
+/// Only `open`, `read`, `seek` and `tell` are required, the rest defaults to `NotImplemented`.
+pub trait FileSystem {
+    type File;
+    type Dir;
+
+    fn open(&mut self, path: &str, mode: OpenMode) -> FsResult<Self::File>;
+
+    fn close(&mut self, _file: Self::File) -> FsResult<()> {
+        Ok(())
+    }
+
+    /// Returns the number of bytes read, 0 at the end of the file
+    fn read(&mut self, file: &mut Self::File, buf: &mut [u8]) -> FsResult<usize>;
+
+    /// Returns the number of bytes written
+    fn write(&mut self, _file: &mut Self::File, _buf: &[u8]) -> FsResult<usize> {
+        Err(FsError::NotImplemented)
+    }
+
+    fn seek(&mut self, file: &mut Self::File, pos: SeekFrom) -> FsResult<()>;
+
+    fn tell(&mut self, file: &mut Self::File) -> FsResult<u32>;
+
+    fn dir_open(&mut self, _path: &str) -> FsResult<Self::Dir> {
+        Err(FsError::NotImplemented)
+    }
+
+    /// Returns the next entry of the directory, `None` at the end
+    fn dir_read(&mut self, _dir: &mut Self::Dir) -> FsResult<Option<DirEntry>> {
+        Err(FsError::NotImplemented)
+    }
+
+    fn dir_close(&mut self, _dir: Self::Dir) -> FsResult<()> {
+        Ok(())
+    }
+}
+
+/// Registers `FileSystem`s under drive letters.
+/// Limitations:
+/// * Like other LVGL drivers, drives can't be unregistered, so their file system is leaked
+/// * When two drives use the same letter, the last one wins
+pub enum Drive {}
+
+impl Drive {
+    /// Registers `fs` under `letter`, and returns it for the application to keep using it
+    pub fn new<F: FileSystem>(_lvgl: &Lvgl, letter: char, fs: F) -> &'static mut F {
+        let fs = Box::leak(Box::new(fs));
+        unsafe { register(letter, fs) };
+        fs
+    }
+}
+
+/// Registers `fs` under `letter`.
+///
+/// # Safety
+/// `fs` must outlive its use by LVGL, i.e. forever.
+pub(crate) unsafe fn register<F: FileSystem>(letter: char, fs: *mut F) {
+    assert!(letter.is_ascii(), "Drive letters must be ASCII");
+
+    // LVGL keeps a pointer to the driver
+    let drv = Box::leak(Box::new(mem::zeroed::<lv_fs_drv_t>()));
+    lvgl_sys::lv_fs_drv_init(drv);
+    drv.letter = letter as u8 as cty::c_char;
+    drv.open_cb = Some(Callbacks::<F>::open);
+    drv.close_cb = Some(Callbacks::<F>::close);
+    drv.read_cb = Some(Callbacks::<F>::read);
+    drv.write_cb = Some(Callbacks::<F>::write);
+    drv.seek_cb = Some(Callbacks::<F>::seek);
+    drv.tell_cb = Some(Callbacks::<F>::tell);
+    drv.dir_open_cb = Some(Callbacks::<F>::dir_open);
+    drv.dir_read_cb = Some(Callbacks::<F>::dir_read);
+    drv.dir_close_cb = Some(Callbacks::<F>::dir_close);
+    drv.user_data = fs as *mut cty::c_void;
+    lvgl_sys::lv_fs_drv_register(drv);
+}
+
+fn result(res: FsResult<()>) -> lv_fs_res_t {
+    match res {
+        Ok(()) => lvgl_sys::LV_FS_RES_OK as lv_fs_res_t,
+        Err(err) => err.into(),
+    }
+}
+
+struct Callbacks<F> {
+    _phantom: PhantomData<F>,
+}
+
+// Files and directories are boxed, LVGL only sees pointers to them
+impl<F: FileSystem> Callbacks<F> {
+    unsafe fn fs<'a>(drv: *mut lv_fs_drv_t) -> &'a mut F {
+        &mut *((*drv).user_data as *mut F)
+    }
+
+    unsafe fn path<'a>(path: *const cty::c_char) -> FsResult<&'a str> {
+        CStr::from_ptr(path)
+            .to_str()
+            .map_err(|_| FsError::InvalidParam)
+    }
+
+    unsafe extern "C" fn open(
+        drv: *mut lv_fs_drv_t,
+        path: *const cty::c_char,
+        mode: lvgl_sys::lv_fs_mode_t,
+    ) -> *mut cty::c_void {
+        let mode = OpenMode::from_bits_truncate(mode);
+        match Self::path(path).and_then(|path| Self::fs(drv).open(path, mode)) {
+            Ok(file) => Box::into_raw(Box::new(file)) as *mut cty::c_void,
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    unsafe extern "C" fn close(drv: *mut lv_fs_drv_t, file: *mut cty::c_void) -> lv_fs_res_t {
+        let file = Box::from_raw(file as *mut F::File);
+        result(Self::fs(drv).close(*file))
+    }
+
+    unsafe extern "C" fn read(
+        drv: *mut lv_fs_drv_t,
+        file: *mut cty::c_void,
+        buf: *mut cty::c_void,
+        btr: u32,
+        br: *mut u32,
+    ) -> lv_fs_res_t {
+        let file = &mut *(file as *mut F::File);
+        let buf = slice::from_raw_parts_mut(buf as *mut u8, btr as usize);
+        result(Self::fs(drv).read(file, buf).map(|n| {
+            if let Some(br) = br.as_mut() {
+                *br = n as u32;
+            }
+        }))
+    }
+
+    unsafe extern "C" fn write(
+        drv: *mut lv_fs_drv_t,
+        file: *mut cty::c_void,
+        buf: *const cty::c_void,
+        btw: u32,
+        bw: *mut u32,
+    ) -> lv_fs_res_t {
+        let file = &mut *(file as *mut F::File);
+        let buf = slice::from_raw_parts(buf as *const u8, btw as usize);
+        result(Self::fs(drv).write(file, buf).map(|n| {
+            if let Some(bw) = bw.as_mut() {
+                *bw = n as u32;
+            }
+        }))
+    }
+
+    unsafe extern "C" fn seek(
+        drv: *mut lv_fs_drv_t,
+        file: *mut cty::c_void,
+        pos: u32,
+        whence: lvgl_sys::lv_fs_whence_t,
+    ) -> lv_fs_res_t {
+        let file = &mut *(file as *mut F::File);
+        let pos = match whence {
+            lvgl_sys::lv_fs_whence_t_LV_FS_SEEK_SET => SeekFrom::Start(pos),
+            lvgl_sys::lv_fs_whence_t_LV_FS_SEEK_CUR => SeekFrom::Current(pos),
+            lvgl_sys::lv_fs_whence_t_LV_FS_SEEK_END => SeekFrom::End(pos),
+            _ => return FsError::InvalidParam.into(),
+        };
+        result(Self::fs(drv).seek(file, pos))
+    }
+
+    unsafe extern "C" fn tell(
+        drv: *mut lv_fs_drv_t,
+        file: *mut cty::c_void,
+        pos: *mut u32,
+    ) -> lv_fs_res_t {
+        let file = &mut *(file as *mut F::File);
+        result(Self::fs(drv).tell(file).map(|p| *pos = p))
+    }
+
+    unsafe extern "C" fn dir_open(
+        drv: *mut lv_fs_drv_t,
+        path: *const cty::c_char,
+    ) -> *mut cty::c_void {
+        match Self::path(path).and_then(|path| Self::fs(drv).dir_open(path)) {
+            Ok(dir) => Box::into_raw(Box::new(dir)) as *mut cty::c_void,
+            Err(_) => ptr::null_mut(),
+        }
+    }
+
+    /// LVGL expects the name in `name`, prefixed with `/` for directories, and an empty name
+    /// at the end. The buffer is `LV_FS_MAX_FN_LENGTH` long by convention.
+    unsafe extern "C" fn dir_read(
+        drv: *mut lv_fs_drv_t,
+        dir: *mut cty::c_void,
+        name: *mut cty::c_char,
+    ) -> lv_fs_res_t {
+        let dir = &mut *(dir as *mut F::Dir);
+        let buf =
+            slice::from_raw_parts_mut(name as *mut u8, lvgl_sys::LV_FS_MAX_FN_LENGTH as usize);
+        result(Self::fs(drv).dir_read(dir).map(|entry| {
+            let mut len = 0;
+            if let Some(entry) = entry {
+                let prefix: &[u8] = if entry.is_dir { b"/" } else { b"" };
+                for &b in prefix
+                    .iter()
+                    .chain(entry.name.as_bytes())
+                    .take(buf.len() - 1)
+                {
+                    buf[len] = b;
+                    len += 1;
+                }
+            }
+            buf[len] = 0;
+        }))
+    }
+
+    unsafe extern "C" fn dir_close(drv: *mut lv_fs_drv_t, dir: *mut cty::c_void) -> lv_fs_res_t {
+        let dir = Box::from_raw(dir as *mut F::Dir);
+        result(Self::fs(drv).dir_close(*dir))
+    }
+}
+
+/// A read-only file system of files in memory, usually bundled with `include_assets!`.
+pub struct MemFs {
+    files: &'static [(&'static str, &'static [u8])],
+}
+
+impl MemFs {
+    /// `files` maps paths, without leading `/` and with `/` separators, to their content
+    pub const fn new(files: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self { files }
+    }
+
+    /// The content of a file
+    pub fn get(&self, path: &str) -> Option<&'static [u8]> {
+        let path = path.trim_start_matches('/');
+        self.files
+            .iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, data)| *data)
+    }
+}
+
+/// A file in memory
+pub struct MemFile {
+    data: &'static [u8],
+    pos: usize,
+}
+
+impl MemFile {
+    pub fn new(data: &'static [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    pub(crate) fn read(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        n
+    }
+
+    pub(crate) fn seek(&mut self, pos: SeekFrom) {
+        let pos = match pos {
+            SeekFrom::Start(pos) => pos as usize,
+            SeekFrom::Current(pos) => self.pos + pos as usize,
+            SeekFrom::End(pos) => self.data.len() + pos as usize,
+        };
+        self.pos = pos.min(self.data.len());
+    }
+
+    pub(crate) fn tell(&self) -> u32 {
+        self.pos as u32
+    }
+}
+
+/// Implements the file operations of a `FileSystem` for `MemFile`s
+macro_rules! mem_file_ops {
+    () => {
+        fn read(&mut self, file: &mut MemFile, buf: &mut [u8]) -> FsResult<usize> {
+            Ok(file.read(buf))
+        }
+
+        fn seek(&mut self, file: &mut MemFile, pos: SeekFrom) -> FsResult<()> {
+            file.seek(pos);
+            Ok(())
+        }
+
+        fn tell(&mut self, file: &mut MemFile) -> FsResult<u32> {
+            Ok(file.tell())
+        }
+    };
+}
+pub(crate) use mem_file_ops;
+
+impl FileSystem for MemFs {
+    type File = MemFile;
+    type Dir = vec::IntoIter<DirEntry>;
+
+    fn open(&mut self, path: &str, mode: OpenMode) -> FsResult<MemFile> {
+        if mode.contains(OpenMode::WRITE) {
+            return Err(FsError::Denied);
+        }
+        self.get(path).map(MemFile::new).ok_or(FsError::NotFound)
+    }
+
+    mem_file_ops!();
+
+    fn dir_open(&mut self, path: &str) -> FsResult<Self::Dir> {
+        let path = path.trim_matches('/');
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", path)
+        };
+
+        let mut entries: Vec<DirEntry> = Vec::new();
+        for (name, _) in self.files {
+            let name = match name.strip_prefix(prefix.as_str()) {
+                Some(name) => name,
+                None => continue,
+            };
+            let entry = match name.split_once('/') {
+                Some((dir, _)) => DirEntry {
+                    name: dir.into(),
+                    is_dir: true,
+                },
+                None => DirEntry {
+                    name: name.into(),
+                    is_dir: false,
+                },
+            };
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+
+        if entries.is_empty() && !path.is_empty() {
+            return Err(FsError::NotFound);
+        }
+        Ok(entries.into_iter())
+    }
+
+    fn dir_read(&mut self, dir: &mut Self::Dir) -> FsResult<Option<DirEntry>> {
+        Ok(dir.next())
+    }
+}
+
+/// A directory of the host, e.g. `Drive::new(&lvgl, 'S', StdFs::new("assets"))`
+#[cfg(feature = "std")]
+pub struct StdFs {
+    root: std::path::PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl StdFs {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Paths can't leave the root, neither with `..` nor by being absolute, e.g. `C:\`.
+    /// Symbolic links inside of the root are followed though.
+    fn path(&self, path: &str) -> FsResult<std::path::PathBuf> {
+        use std::path::{Component, Path};
+        let path = Path::new(path.trim_start_matches(|c| c == '/' || c == '\\'));
+        if !path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(FsError::Denied);
+        }
+        Ok(self.root.join(path))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FsError {
+    fn from(err: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        match err.kind() {
+            ErrorKind::NotFound => FsError::NotFound,
+            ErrorKind::PermissionDenied => FsError::Denied,
+            ErrorKind::TimedOut => FsError::Timeout,
+            ErrorKind::WouldBlock | ErrorKind::Interrupted => FsError::Busy,
+            ErrorKind::InvalidInput => FsError::InvalidParam,
+            ErrorKind::OutOfMemory => FsError::OutOfMemory,
+            _ => FsError::Unknown,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FileSystem for StdFs {
+    type File = std::fs::File;
+    type Dir = std::fs::ReadDir;
+
+    fn open(&mut self, path: &str, mode: OpenMode) -> FsResult<Self::File> {
+        Ok(std::fs::OpenOptions::new()
+            .read(mode.contains(OpenMode::READ))
+            .write(mode.contains(OpenMode::WRITE))
+            .create(mode.contains(OpenMode::WRITE))
+            .open(self.path(path)?)?)
+    }
+
+    fn read(&mut self, file: &mut Self::File, buf: &mut [u8]) -> FsResult<usize> {
+        use std::io::Read;
+        Ok(file.read(buf)?)
+    }
+
+    fn write(&mut self, file: &mut Self::File, buf: &[u8]) -> FsResult<usize> {
+        use std::io::Write;
+        Ok(file.write(buf)?)
+    }
+
+    fn seek(&mut self, file: &mut Self::File, pos: SeekFrom) -> FsResult<()> {
+        use std::io::Seek;
+        let pos = match pos {
+            SeekFrom::Start(pos) => std::io::SeekFrom::Start(pos as u64),
+            SeekFrom::Current(pos) => std::io::SeekFrom::Current(pos as i64),
+            SeekFrom::End(pos) => std::io::SeekFrom::End(pos as i64),
+        };
+        file.seek(pos)?;
+        Ok(())
+    }
+
+    fn tell(&mut self, file: &mut Self::File) -> FsResult<u32> {
+        use std::io::Seek;
+        Ok(file.stream_position()? as u32)
+    }
+
+    fn dir_open(&mut self, path: &str) -> FsResult<Self::Dir> {
+        Ok(std::fs::read_dir(self.path(path)?)?)
+    }
+
+    fn dir_read(&mut self, dir: &mut Self::Dir) -> FsResult<Option<DirEntry>> {
+        match dir.next() {
+            Some(entry) => {
+                let entry = entry?;
+                Ok(Some(DirEntry {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    is_dir: entry.file_type()?.is_dir(),
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+}
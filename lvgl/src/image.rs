@@ -0,0 +1,305 @@
+use core::{mem, ptr};
+use cstr_core::CStr;
+use embedded_graphics::image::ImageRaw;
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    image::ImageDrawable,
+    pixelcolor::raw::{self, RawData},
+    primitives::{PointsIter, Rectangle},
+};
+use lvgl_sys::{lv_img_cf_t, lv_img_dsc_t};
+
+use crate::core::{from_lv_color, PixelColor};
+
+crate::native_enum! {
+    lv_img_cf_t,
+    /// Color format of an image
+    pub enum ColorFormat {
+        /// Pixels are `lv_color_t`, matching `LV_COLOR_DEPTH`
+        TrueColor = lvgl_sys::LV_IMG_CF_TRUE_COLOR,
+        /// Pixels are `lv_color_t` followed by an alpha byte
+        TrueColorAlpha = lvgl_sys::LV_IMG_CF_TRUE_COLOR_ALPHA,
+        /// Pixels of the `LV_COLOR_CHROMA_KEY` color are transparent
+        TrueColorChromaKeyed = lvgl_sys::LV_IMG_CF_TRUE_COLOR_CHROMA_KEYED,
+        Indexed1Bit = lvgl_sys::LV_IMG_CF_INDEXED_1BIT,
+        Indexed2Bit = lvgl_sys::LV_IMG_CF_INDEXED_2BIT,
+        Indexed4Bit = lvgl_sys::LV_IMG_CF_INDEXED_4BIT,
+        Indexed8Bit = lvgl_sys::LV_IMG_CF_INDEXED_8BIT,
+        /// Only an alpha channel, drawn with the recolor of the style
+        Alpha1Bit = lvgl_sys::LV_IMG_CF_ALPHA_1BIT,
+        Alpha2Bit = lvgl_sys::LV_IMG_CF_ALPHA_2BIT,
+        Alpha4Bit = lvgl_sys::LV_IMG_CF_ALPHA_4BIT,
+        Alpha8Bit = lvgl_sys::LV_IMG_CF_ALPHA_8BIT,
+    }
+}
+
+/// `lv_color_t` is stored in the byte order of the target
+#[cfg(target_endian = "little")]
+pub type ColorByteOrder = raw::LittleEndian;
+#[cfg(target_endian = "big")]
+pub type ColorByteOrder = raw::BigEndian;
+
+/// An image in memory, see `include_image!` to embed a PNG.
+#[repr(transparent)]
+pub struct ImageDsc {
+    raw: lv_img_dsc_t,
+}
+
+// Images are never mutated once created.
+unsafe impl Sync for ImageDsc {}
+
+impl ImageDsc {
+    /// Describes `data`, laid out as expected by LVGL for the color format.
+    /// `width` and `height` are at most 2047.
+    pub const fn new(cf: ColorFormat, width: u16, height: u16, data: &'static [u8]) -> Self {
+        assert!(width < 1 << 11 && height < 1 << 11);
+        // cf:5, always_zero:3, reserved:2, w:11, h:11. LVGL reverses the fields on big endian
+        // systems, so the value of the word is the same everywhere.
+        let header = cf as u32 | (width as u32) << 10 | (height as u32) << 21;
+        Self {
+            raw: lv_img_dsc_t {
+                header: lvgl_sys::lv_img_header_t {
+                    _bitfield_align_1: [],
+                    _bitfield_1: lvgl_sys::__BindgenBitfieldUnit::new(header.to_ne_bytes()),
+                },
+                data_size: data.len() as u32,
+                data: data.as_ptr(),
+            },
+        }
+    }
+
+    pub fn raw(&self) -> &lv_img_dsc_t {
+        &self.raw
+    }
+
+    pub fn color_format(&self) -> ColorFormat {
+        use core::convert::TryFrom;
+        ColorFormat::try_from(self.raw.header.cf() as lv_img_cf_t).unwrap()
+    }
+
+    pub fn width(&self) -> u16 {
+        self.raw.header.w() as u16
+    }
+
+    pub fn height(&self) -> u16 {
+        self.raw.header.h() as u16
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.raw.data, self.raw.data_size as usize) }
+    }
+
+    /// The color of a pixel, for the true color formats only
+    pub fn pixel(&self, x: u16, y: u16) -> Option<PixelColor> {
+        let color_size = mem::size_of::<lvgl_sys::lv_color_t>();
+        let stride = match self.color_format() {
+            ColorFormat::TrueColor | ColorFormat::TrueColorChromaKeyed => color_size,
+            ColorFormat::TrueColorAlpha => lvgl_sys::LV_IMG_PX_SIZE_ALPHA_BYTE as usize,
+            _ => return None,
+        };
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+        let offset = (y as usize * self.width() as usize + x as usize) * stride;
+        let bytes = self.data().get(offset..offset + color_size)?;
+        let color = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const lvgl_sys::lv_color_t) };
+        Some(from_lv_color(color))
+    }
+
+    /// The pixels as an `ImageRaw`, for the `TrueColor` format when `lv_color_t` is stored like
+    /// `PixelColor`, i.e. at 16 bits without `LV_COLOR_16_SWAP`
+    pub fn as_image_raw(&self) -> Option<ImageRaw<'_, PixelColor, ColorByteOrder>> {
+        type Raw = <PixelColor as embedded_graphics_core::pixelcolor::PixelColor>::Raw;
+        let color_size = mem::size_of::<lvgl_sys::lv_color_t>();
+        if self.color_format() != ColorFormat::TrueColor
+            || color_size * 8 != Raw::BITS_PER_PIXEL
+            || lvgl_sys::LV_COLOR_16_SWAP != 0
+        {
+            return None;
+        }
+        let len = self.width() as usize * self.height() as usize * color_size;
+        Some(ImageRaw::new(self.data().get(..len)?, self.width() as u32))
+    }
+}
+
+impl OriginDimensions for ImageDsc {
+    fn size(&self) -> Size {
+        Size::new(self.width() as u32, self.height() as u32)
+    }
+}
+
+/// True color images, snapshots included, can be drawn like an `ImageRaw` with
+/// `embedded_graphics::image::Image`. Images of the other formats draw nothing.
+impl ImageDrawable for ImageDsc {
+    type Color = PixelColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = PixelColor>,
+    {
+        self.draw_sub_image(target, &self.bounding_box())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = PixelColor>,
+    {
+        if !matches!(
+            self.color_format(),
+            ColorFormat::TrueColor
+                | ColorFormat::TrueColorAlpha
+                | ColorFormat::TrueColorChromaKeyed
+        ) {
+            return Ok(());
+        }
+        let area = area.intersection(&self.bounding_box());
+        // Truncated data is drawn with the default color
+        let colors = area
+            .points()
+            .map(|p| self.pixel(p.x as u16, p.y as u16).unwrap_or_default());
+        target.fill_contiguous(&Rectangle::new(Point::zero(), area.size), colors)
+    }
+}
+
+/// Picks the pixels generated by `include_image!` for the configured `LV_COLOR_DEPTH`.
+#[doc(hidden)]
+pub const fn __select_color_depth(data: [&'static [u8]; 5]) -> &'static [u8] {
+    match (lvgl_sys::LV_COLOR_DEPTH, lvgl_sys::LV_COLOR_16_SWAP) {
+        (1, _) => data[0],
+        (8, _) => data[1],
+        (16, 0) => data[2],
+        (16, _) => data[3],
+        _ => data[4],
+    }
+}
+
+/// What an image shows, as accepted by `lv_img_set_src()` and the `BG_IMG_SRC` style.
+#[derive(Clone, Copy)]
+pub enum ImageSource<'a> {
+    /// An image in memory
+    Dsc(&'static ImageDsc),
+    /// A symbol of the font, e.g. `LV_SYMBOL_OK`, see `ImageSource::symbol()`
+    #[non_exhaustive]
+    Symbol(&'a CStr),
+    /// A file on a registered drive, e.g. `S:/images/logo.bin`, see `ImageSource::file()`
+    #[non_exhaustive]
+    File(&'a CStr),
+}
+
+impl<'a> ImageSource<'a> {
+    /// `None` unless `symbol` starts with a non-ASCII byte, as the UTF-8 of the symbols do
+    pub fn symbol(symbol: &'a CStr) -> Option<Self> {
+        match symbol.to_bytes().first() {
+            Some(0x80..=0xff) => Some(ImageSource::Symbol(symbol)),
+            _ => None,
+        }
+    }
+
+    /// `None` unless `path` starts with a printable ASCII character, as drive letters do
+    pub fn file(path: &'a CStr) -> Option<Self> {
+        match path.to_bytes().first() {
+            Some(0x20..=0x7e) => Some(ImageSource::File(path)),
+            _ => None,
+        }
+    }
+
+    /// LVGL tells the sources apart by their first byte: symbols start with a non-ASCII byte,
+    /// paths with a printable character, and image headers with neither.
+    pub fn as_ptr(&self) -> *const cty::c_void {
+        match self {
+            ImageSource::Dsc(dsc) => dsc.raw() as *const _ as *const cty::c_void,
+            ImageSource::Symbol(s) | ImageSource::File(s) => s.as_ptr() as *const cty::c_void,
+        }
+    }
+
+    pub(crate) fn opt_ptr(src: Option<Self>) -> *const cty::c_void {
+        src.map_or(ptr::null(), |src| src.as_ptr())
+    }
+}
+
+impl From<&'static ImageDsc> for ImageSource<'_> {
+    fn from(dsc: &'static ImageDsc) -> Self {
+        ImageSource::Dsc(dsc)
+    }
+}
+
+/// An object rendered by LVGL, see `ObjExt::snapshot()`. The image is freed when dropped.
+#[cfg(lv_use_snapshot)]
+pub struct Snapshot {
+    dsc: ptr::NonNull<lv_img_dsc_t>,
+}
+
+#[cfg(lv_use_snapshot)]
+impl Snapshot {
+    pub(crate) fn take(obj: &mut lvgl_sys::lv_obj_t, cf: ColorFormat) -> Option<Self> {
+        let dsc = unsafe { lvgl_sys::lv_snapshot_take(obj, cf.into()) };
+        ptr::NonNull::new(dsc).map(|dsc| Self { dsc })
+    }
+
+    /// Keeps the image forever, e.g. to use it as an `ImageSource`
+    pub fn leak(self) -> &'static ImageDsc {
+        let dsc = self.dsc;
+        mem::forget(self);
+        unsafe { &*(dsc.as_ptr() as *const ImageDsc) }
+    }
+}
+
+#[cfg(lv_use_snapshot)]
+impl core::ops::Deref for Snapshot {
+    type Target = ImageDsc;
+
+    fn deref(&self) -> &ImageDsc {
+        // ImageDsc is a transparent wrapper
+        unsafe { &*(self.dsc.as_ptr() as *const ImageDsc) }
+    }
+}
+
+#[cfg(lv_use_snapshot)]
+impl Drop for Snapshot {
+    fn drop(&mut self) {
+        unsafe { lvgl_sys::lv_snapshot_free(self.dsc.as_ptr()) };
+    }
+}
+
+/// An object rendered by LVGL into a buffer, see `ObjExt::snapshot_to_buf()`
+#[cfg(lv_use_snapshot)]
+pub struct SnapshotRef<'a> {
+    dsc: ImageDsc,
+    _buf: core::marker::PhantomData<&'a mut [u8]>,
+}
+
+#[cfg(lv_use_snapshot)]
+impl<'a> SnapshotRef<'a> {
+    pub(crate) fn take(
+        obj: &mut lvgl_sys::lv_obj_t,
+        cf: ColorFormat,
+        buf: &'a mut [u8],
+    ) -> Option<Self> {
+        let mut dsc = ImageDsc {
+            raw: unsafe { mem::zeroed() },
+        };
+        let res = unsafe {
+            lvgl_sys::lv_snapshot_take_to_buf(
+                obj,
+                cf.into(),
+                &mut dsc.raw,
+                buf.as_mut_ptr() as *mut cty::c_void,
+                buf.len() as u32,
+            )
+        };
+        (res == lvgl_sys::LV_RES_OK as lvgl_sys::lv_res_t).then(|| Self {
+            dsc,
+            _buf: core::marker::PhantomData,
+        })
+    }
+}
+
+#[cfg(lv_use_snapshot)]
+impl core::ops::Deref for SnapshotRef<'_> {
+    type Target = ImageDsc;
+
+    fn deref(&self) -> &ImageDsc {
+        &self.dsc
+    }
+}
@@ -17,6 +17,8 @@
 #[macro_use]
 extern crate bitflags;
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[cfg(feature = "lvgl_alloc")]
 pub mod allocator;
@@ -26,6 +28,17 @@ pub mod core;
 
 pub mod widgets;
 
+pub mod font;
+
+pub mod image;
+
+pub mod draw;
+
+pub mod fs;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 pub mod style;
 pub(crate) use style::native_enum;
 
@@ -35,3 +48,4 @@ pub mod prelude {
 
 pub use lvgl_sys as sys;
 pub use cstr_core;
+pub use lvgl_macros::{include_assets, include_image};
@@ -0,0 +1,19 @@
+bitflags! {
+    /// Directions, e.g. of a chart cursor or of scrolling
+    pub struct Dir: lvgl_sys::lv_dir_t {
+        const NONE = lvgl_sys::LV_DIR_NONE;
+        const LEFT = lvgl_sys::LV_DIR_LEFT;
+        const RIGHT = lvgl_sys::LV_DIR_RIGHT;
+        const TOP = lvgl_sys::LV_DIR_TOP;
+        const BOTTOM = lvgl_sys::LV_DIR_BOTTOM;
+        const HOR = lvgl_sys::LV_DIR_HOR;
+        const VER = lvgl_sys::LV_DIR_VER;
+        const ALL = lvgl_sys::LV_DIR_ALL;
+    }
+}
+
+impl From<Dir> for lvgl_sys::lv_dir_t {
+    fn from(dir: Dir) -> Self {
+        dir.bits()
+    }
+}
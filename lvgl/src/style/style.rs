@@ -1,6 +1,9 @@
 use alloc::boxed::Box;
 use core::mem;
 
+use crate::font::Font;
+use crate::image::ImageSource;
+
 pub enum Themes {
     Pretty,
 }
@@ -28,6 +31,19 @@ impl Style {
 }
 */
 
+impl Style {
+    pub fn set_text_font(&mut self, font: &'static Font) -> &mut Self {
+        unsafe { lvgl_sys::lv_style_set_text_font(self._raw.as_mut(), font.raw()) };
+        self
+    }
+
+    /// The style keeps a pointer to the source, hence the `'static`
+    pub fn set_bg_img_src(&mut self, src: ImageSource<'static>) -> &mut Self {
+        unsafe { lvgl_sys::lv_style_set_bg_img_src(self._raw.as_mut(), src.as_ptr()) };
+        self
+    }
+}
+
 impl Default for Style {
     fn default() -> Self {
         let raw = unsafe {
@@ -0,0 +1,374 @@
+//! Helpers to test user interfaces on the host, without a window (`testing` feature).
+//!
+//! ```ignore
+//! with_screen::<(), _>(240, 240, |lvgl, display, screen| {
+//!     // ... build the screen
+//!     settle(lvgl, display);
+//!     display.assert_pixel(10, 10, Rgb565::WHITE);
+//!     assert_golden(display, "tests/goldens/screen.png", 0);
+//!
+//!     let mut input = TestInput::new(lvgl, display);
+//!     input.click(lvgl, 120, 120);
+//! });
+//! ```
+
+use core::convert::{Infallible, TryFrom};
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use embedded_graphics_core::{pixelcolor::Rgb888, prelude::*};
+use lvgl_sys::lv_coord_t;
+use std::{
+    env, format,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    string::String,
+    vec::Vec,
+};
+
+use crate::core::{
+    Display, Encoder, Group, InputDevice, Keypad, Lvgl, ObjExt, PixelColor, Screen, Ticks, TouchPad,
+};
+
+/// Simulated time after which `settle()` gives up, in ms
+const SETTLE_TIMEOUT: u32 = 10_000;
+
+/// When set, `assert_golden()` records the screens instead of comparing them
+pub const UPDATE_GOLDENS_ENV: &str = "LVGL_UPDATE_GOLDENS";
+
+/// A display drawing into memory
+pub struct HeadlessDisplay {
+    size: Size,
+    pixels: Vec<PixelColor>,
+}
+
+impl HeadlessDisplay {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: Size::new(width, height),
+            pixels: std::vec![PixelColor::default(); (width * height) as usize],
+        }
+    }
+
+    /// Registers a new headless display with LVGL, with a draw buffer of the size of the
+    /// screen. The buffer is leaked, like the display itself.
+    pub fn register(lvgl: &Lvgl, width: u32, height: u32) -> Display<Self> {
+        let buffer = std::vec![MaybeUninit::uninit(); (width * height) as usize];
+        Display::new(lvgl, Self::new(width, height), buffer.leak())
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> PixelColor {
+        assert!(
+            x < self.size.width && y < self.size.height,
+            "({}, {}) is out of the display",
+            x,
+            y
+        );
+        self.pixels[(y * self.size.width + x) as usize]
+    }
+
+    /// Panics with a readable message if the pixel at `(x, y)` is not `expected`
+    #[track_caller]
+    pub fn assert_pixel(&self, x: u32, y: u32, expected: PixelColor) {
+        let actual = self.pixel(x, y);
+        assert!(
+            actual == expected,
+            "pixel ({}, {}) is {:?}, expected {:?}",
+            x,
+            y,
+            actual,
+            expected
+        );
+    }
+
+    /// The pixels as RGB888, row by row
+    pub fn to_raw(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&color| {
+                let color = Rgb888::from(color);
+                [color.r(), color.g(), color.b()]
+            })
+            .collect()
+    }
+
+    /// The screen as a PNG file
+    pub fn to_png(&self) -> Vec<u8> {
+        encode_png(self.size, &self.to_raw())
+    }
+}
+
+impl DrawTarget for HeadlessDisplay {
+    type Color = PixelColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let (Ok(x), Ok(y)) = (u32::try_from(point.x), u32::try_from(point.y)) {
+                if x < self.size.width && y < self.size.height {
+                    self.pixels[(y * self.size.width + x) as usize] = color;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OriginDimensions for HeadlessDisplay {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// Initializes LVGL with a headless display of `width` x `height`, and runs `f` with a loaded
+/// screen whose context is `C::default()`.
+///
+/// LVGL is not thread safe, so a test binary must call it from a single test. The display is
+/// leaked afterwards, as displays are never unregistered.
+pub fn with_screen<C: Default + 'static, R>(
+    width: u32,
+    height: u32,
+    f: impl FnOnce(&mut Lvgl, &mut Display<HeadlessDisplay>, &mut Screen<C>) -> R,
+) -> R {
+    let mut lvgl = Lvgl::new();
+    let mut display = HeadlessDisplay::register(&lvgl, width, height);
+
+    let mut screen = Screen::<C>::new(&display);
+    *screen.context() = Some(C::default());
+    display.load_screen(&mut screen);
+    settle(&mut lvgl, &display);
+
+    let result = f(&mut lvgl, &mut display, &mut screen);
+    mem::forget(display);
+    result
+}
+
+/// Advances the time of LVGL and runs its tasks until the display is fully redrawn and no
+/// animation is running. Returns `false` if that didn't happen within 10 simulated seconds,
+/// e.g. because of a spinner.
+pub fn settle<T>(lvgl: &mut Lvgl, display: &Display<T>) -> bool {
+    let period = lvgl_sys::LV_DISP_DEF_REFR_PERIOD as u32;
+    let mut ticks = lvgl.ticks();
+    let mut elapsed = 0;
+    while elapsed < SETTLE_TIMEOUT {
+        ticks.inc(period);
+        elapsed += period;
+        lvgl.run_tasks();
+
+        let invalidated = display.disp.inv_p > 0;
+        let animating = unsafe { lvgl_sys::lv_anim_count_running() } > 0;
+        if !invalidated && !animating {
+            return true;
+        }
+    }
+    false
+}
+
+/// Scripted input devices: a pointer, a keypad and an encoder, registered on one display.
+///
+/// Each action advances the time of LVGL and runs its tasks, so the resulting events have
+/// been sent when it returns. Dropping it leaves the devices registered, and released.
+pub struct TestInput {
+    pointer: ManuallyDrop<InputDevice<TouchPad>>,
+    keypad: ManuallyDrop<InputDevice<Keypad>>,
+    encoder: ManuallyDrop<InputDevice<Encoder>>,
+    ticks: Ticks,
+}
+
+impl TestInput {
+    pub fn new<D>(lvgl: &Lvgl, display: &mut Display<D>) -> Self {
+        Self {
+            pointer: ManuallyDrop::new(InputDevice::new(display)),
+            keypad: ManuallyDrop::new(InputDevice::new(display)),
+            encoder: ManuallyDrop::new(InputDevice::new(display)),
+            ticks: lvgl.ticks(),
+        }
+    }
+
+    /// The keypad and the encoder navigate `group`
+    pub fn set_group(&mut self, group: &Group) -> &mut Self {
+        self.keypad.set_group(group);
+        self.encoder.set_group(group);
+        self
+    }
+
+    /// Lets `ms` milliseconds pass, reading the devices as LVGL would
+    pub fn wait(&mut self, lvgl: &mut Lvgl, ms: u32) {
+        let period = lvgl_sys::LV_INDEV_DEF_READ_PERIOD as u32;
+        let mut elapsed = 0;
+        while elapsed < ms {
+            let step = period.min(ms - elapsed);
+            self.ticks.inc(step);
+            elapsed += step;
+            lvgl.run_tasks();
+        }
+    }
+
+    /// Waits long enough for LVGL to read the devices twice
+    fn read(&mut self, lvgl: &mut Lvgl) {
+        self.wait(lvgl, 2 * lvgl_sys::LV_INDEV_DEF_READ_PERIOD as u32);
+    }
+
+    pub fn press(&mut self, lvgl: &mut Lvgl, x: lv_coord_t, y: lv_coord_t) {
+        *self.pointer.state() = TouchPad::Pressed { x, y };
+        self.read(lvgl);
+    }
+
+    pub fn release(&mut self, lvgl: &mut Lvgl) {
+        *self.pointer.state() = TouchPad::Released;
+        self.read(lvgl);
+    }
+
+    pub fn click(&mut self, lvgl: &mut Lvgl, x: lv_coord_t, y: lv_coord_t) {
+        self.press(lvgl, x, y);
+        self.release(lvgl);
+    }
+
+    /// Holds the pointer past the long press time of LVGL
+    pub fn long_press(&mut self, lvgl: &mut Lvgl, x: lv_coord_t, y: lv_coord_t) {
+        self.press(lvgl, x, y);
+        self.wait(lvgl, lvgl_sys::LV_INDEV_DEF_LONG_PRESS_TIME as u32);
+        self.release(lvgl);
+    }
+
+    /// Presses at `from`, moves to `to` in `steps` reads, and releases
+    pub fn drag(
+        &mut self,
+        lvgl: &mut Lvgl,
+        from: (lv_coord_t, lv_coord_t),
+        to: (lv_coord_t, lv_coord_t),
+        steps: lv_coord_t,
+    ) {
+        let steps = steps.max(1);
+        self.press(lvgl, from.0, from.1);
+        for i in 1..=steps {
+            let x = from.0 + (to.0 - from.0) * i / steps;
+            let y = from.1 + (to.1 - from.1) * i / steps;
+            *self.pointer.state() = TouchPad::Pressed { x, y };
+            self.wait(lvgl, lvgl_sys::LV_INDEV_DEF_READ_PERIOD as u32);
+        }
+        self.release(lvgl);
+    }
+
+    /// Presses and releases a `Key`, or a character
+    pub fn press_key(&mut self, lvgl: &mut Lvgl, key: impl Into<u32>) {
+        let state = self.keypad.state();
+        state.key = key.into();
+        state.pressed = true;
+        self.read(lvgl);
+        self.keypad.state().pressed = false;
+        self.read(lvgl);
+    }
+
+    /// Turns the encoder by `steps`, negative to the left
+    pub fn turn_encoder(&mut self, lvgl: &mut Lvgl, steps: i16) {
+        self.encoder.state().diff += steps;
+        self.read(lvgl);
+    }
+
+    pub fn press_encoder(&mut self, lvgl: &mut Lvgl) {
+        self.encoder.state().pressed = true;
+        self.read(lvgl);
+        self.encoder.state().pressed = false;
+        self.read(lvgl);
+    }
+}
+
+/// Compares the display with a golden PNG. Pixels can differ by `tolerance` on each channel.
+///
+/// On mismatch, `<golden>.actual.png` and `<golden>.diff.png` are written next to the golden,
+/// the diff showing the differing pixels in red. Goldens are recorded instead when the
+/// `LVGL_UPDATE_GOLDENS` environment variable is set, a missing golden fails otherwise.
+#[track_caller]
+pub fn assert_golden(display: &HeadlessDisplay, golden: impl AsRef<Path>, tolerance: u8) {
+    let golden = golden.as_ref();
+    let actual = display.to_raw();
+
+    if env::var_os(UPDATE_GOLDENS_ENV).is_some() {
+        if let Some(dir) = golden.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(golden, encode_png(display.size, &actual)).unwrap();
+        return;
+    }
+
+    let (size, expected) = decode_png(golden).unwrap_or_else(|e| {
+        panic!(
+            "Can't read golden {}: {}. Set {}=1 to record it.",
+            golden.display(),
+            e,
+            UPDATE_GOLDENS_ENV
+        )
+    });
+
+    let mismatch = if size != display.size {
+        Some(format!("size is {:?}, expected {:?}", display.size, size))
+    } else {
+        let mut diff = Vec::with_capacity(actual.len());
+        let mut count = 0;
+        for (a, e) in actual.chunks(3).zip(expected.chunks(3)) {
+            if a.iter().zip(e).any(|(a, e)| a.abs_diff(*e) > tolerance) {
+                count += 1;
+                diff.extend_from_slice(&[255, 0, 0]);
+            } else {
+                let luma = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 9) as u8;
+                diff.extend_from_slice(&[luma, luma, luma]);
+            }
+        }
+        if count > 0 {
+            fs::write(sibling(golden, "diff"), encode_png(size, &diff)).unwrap();
+            Some(format!("{} pixels differ", count))
+        } else {
+            None
+        }
+    };
+
+    if let Some(mismatch) = mismatch {
+        let actual_path = sibling(golden, "actual");
+        fs::write(&actual_path, encode_png(display.size, &actual)).unwrap();
+        panic!(
+            "Screen doesn't match golden {}: {}. See {}, and set {}=1 to update the golden.",
+            golden.display(),
+            mismatch,
+            actual_path.display(),
+            UPDATE_GOLDENS_ENV
+        );
+    }
+}
+
+/// `screen.png` -> `screen.<suffix>.png`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+fn encode_png(size: Size, rgb: &[u8]) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size.width, size.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(rgb).unwrap();
+    writer.finish().unwrap();
+    png
+}
+
+/// Decodes a PNG as RGB888, dropping the alpha channel if any
+fn decode_png(path: &Path) -> Result<(Size, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(File::open(path).map_err(|e| e.to_string())?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+    let mut buf = std::vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|e| e.to_string())?;
+    let buf = &buf[..info.buffer_size()];
+
+    let rgb = match info.color_type {
+        png::ColorType::Rgb => buf.to_vec(),
+        png::ColorType::Rgba => buf.chunks(4).flat_map(|p| [p[0], p[1], p[2]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&l| [l, l, l]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks(2).flat_map(|p| [p[0], p[0], p[0]]).collect(),
+        png::ColorType::Indexed => return Err("indexed PNGs should have been expanded".into()),
+    };
+    Ok((Size::new(info.width, info.height), rgb))
+}
@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+use cstr_core::{CStr, CString};
+use cty::c_char;
+
+use crate::core::{add_event_cb, attached, Event};
+use crate::widgets::Btnmatrix;
+
+bitflags! {
+    /// How a button of a matrix behaves. Its width is set separately with `set_btn_width()`.
+    pub struct BtnmatrixCtrl: lvgl_sys::lv_btnmatrix_ctrl_t {
+        const HIDDEN = lvgl_sys::LV_BTNMATRIX_CTRL_HIDDEN;
+        const NO_REPEAT = lvgl_sys::LV_BTNMATRIX_CTRL_NO_REPEAT;
+        const DISABLED = lvgl_sys::LV_BTNMATRIX_CTRL_DISABLED;
+        const CHECKABLE = lvgl_sys::LV_BTNMATRIX_CTRL_CHECKABLE;
+        const CHECKED = lvgl_sys::LV_BTNMATRIX_CTRL_CHECKED;
+        /// Send `ValueChanged` on click instead of on press
+        const CLICK_TRIG = lvgl_sys::LV_BTNMATRIX_CTRL_CLICK_TRIG;
+        const POPOVER = lvgl_sys::LV_BTNMATRIX_CTRL_POPOVER;
+        /// Allow recoloring the text with `#color`
+        const RECOLOR = lvgl_sys::LV_BTNMATRIX_CTRL_RECOLOR;
+        const CUSTOM_1 = lvgl_sys::LV_BTNMATRIX_CTRL_CUSTOM_1;
+        const CUSTOM_2 = lvgl_sys::LV_BTNMATRIX_CTRL_CUSTOM_2;
+    }
+}
+
+impl BtnmatrixCtrl {
+    /// A button `width` times as wide as a button of width 1, from 1 to 7. Combine it with the
+    /// other controls, e.g. `BtnmatrixCtrl::width(2) | BtnmatrixCtrl::CHECKABLE`.
+    pub fn width(width: u8) -> Self {
+        assert!((1..=7).contains(&width), "Button widths go from 1 to 7");
+        // The width is stored in the lowest bits, which are not flags
+        unsafe { Self::from_bits_unchecked(width as lvgl_sys::lv_btnmatrix_ctrl_t) }
+    }
+}
+
+impl From<BtnmatrixCtrl> for lvgl_sys::lv_btnmatrix_ctrl_t {
+    fn from(ctrl: BtnmatrixCtrl) -> Self {
+        ctrl.bits()
+    }
+}
+
+const ROW_END: &[u8] = b"\n\0";
+const MAP_END: &[u8] = b"\0";
+
+/// The texts of the buttons as LVGL wants them: rows separated by `"\n"`, ending with `""`
+#[derive(Default)]
+pub(crate) struct BtnMap {
+    texts: Vec<CString>,
+    map: Vec<*const c_char>,
+}
+
+impl BtnMap {
+    pub(crate) fn new(rows: &[&[&str]]) -> Self {
+        let mut texts = Vec::new();
+        let mut map = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                map.push(ROW_END.as_ptr() as *const c_char);
+            }
+            for text in row.iter() {
+                assert!(
+                    !text.is_empty() && *text != "\n",
+                    "Button texts can't be empty or \"\\n\""
+                );
+                let text = CString::new(*text).expect("Button text with a nul byte");
+                // The heap buffer of the CString doesn't move with it
+                map.push(text.as_ptr());
+                texts.push(text);
+            }
+        }
+        map.push(MAP_END.as_ptr() as *const c_char);
+        Self { texts, map }
+    }
+
+    pub(crate) fn btn_count(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// LVGL only reads the map, but wants it mutable
+    pub(crate) fn as_ptr(&mut self) -> *mut *const c_char {
+        self.map.as_mut_ptr()
+    }
+}
+
+impl<C: 'static> Btnmatrix<C> {
+    /// Set the texts of the buttons, row by row. The map is kept until it is replaced or the
+    /// button matrix is deleted.
+    pub fn set_map(&mut self, rows: &[&[&str]]) -> &mut Self {
+        let mut map = BtnMap::new(rows);
+        unsafe { lvgl_sys::lv_btnmatrix_set_map(&mut *self.raw, map.as_ptr()) };
+        // The previous map is dropped only once LVGL doesn't point to it anymore
+        *attached::<BtnMap>(&mut *self.raw) = map;
+        self
+    }
+
+    /// Set the controls of all the buttons, in the order of the map. LVGL copies them.
+    pub fn set_ctrl_map(&mut self, ctrl_map: &[BtnmatrixCtrl]) -> &mut Self {
+        assert_eq!(
+            ctrl_map.len(),
+            self.btn_count() as usize,
+            "One control per button"
+        );
+        let ctrl_map: Vec<lvgl_sys::lv_btnmatrix_ctrl_t> =
+            ctrl_map.iter().map(|ctrl| ctrl.bits()).collect();
+        unsafe { lvgl_sys::lv_btnmatrix_set_ctrl_map(&mut *self.raw, ctrl_map.as_ptr()) };
+        self
+    }
+
+    pub fn has_btn_ctrl(&mut self, btn_id: u16, ctrl: BtnmatrixCtrl) -> bool {
+        unsafe { lvgl_sys::lv_btnmatrix_has_btn_ctrl(&mut *self.raw, btn_id, ctrl.into()) }
+    }
+
+    /// Whether at most one button can be checked at a time, see `set_one_checked()`
+    pub fn one_checked(&self) -> bool {
+        unsafe { lvgl_sys::lv_btnmatrix_get_one_checked(&*self.raw) }
+    }
+
+    pub fn btn_count(&self) -> u16 {
+        let btnm = &*self.raw as *const lvgl_sys::lv_obj_t as *const lvgl_sys::lv_btnmatrix_t;
+        unsafe { (*btnm).btn_cnt }
+    }
+
+    /// The last pressed or released button
+    pub fn selected_btn(&self) -> Option<u16> {
+        let btn_id = unsafe { lvgl_sys::lv_btnmatrix_get_selected_btn(&*self.raw) };
+        if btn_id == lvgl_sys::LV_BTNMATRIX_BTN_NONE as u16 {
+            None
+        } else {
+            Some(btn_id)
+        }
+    }
+
+    pub fn btn_text(&self, btn_id: u16) -> Option<&str> {
+        let text = unsafe { lvgl_sys::lv_btnmatrix_get_btn_text(&*self.raw, btn_id) };
+        if text.is_null() {
+            None
+        } else {
+            unsafe { CStr::from_ptr(text) }.to_str().ok()
+        }
+    }
+
+    /// Register a callback for the `ValueChanged` event, receiving the index and the text of the
+    /// button
+    pub fn on_value_changed(
+        &mut self,
+        mut f: impl FnMut(&mut C, u16, &str) + 'static,
+    ) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, _child| {
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                let btn_id = unsafe { lvgl_sys::lv_btnmatrix_get_selected_btn(current_target) };
+                let text = unsafe { lvgl_sys::lv_btnmatrix_get_btn_text(current_target, btn_id) };
+                if !text.is_null() {
+                    let text = unsafe { CStr::from_ptr(text) };
+                    f(context, btn_id, text.to_str().unwrap_or_default())
+                }
+            },
+        );
+        self
+    }
+}
@@ -0,0 +1,204 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use lvgl_sys::{lv_calendar_date_t, lv_obj_t};
+
+use crate::core::{add_event_cb, attached, Event, Obj};
+use crate::widgets::Calendar;
+
+/// A day of a calendar. Months and days start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl CalendarDate {
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+}
+
+impl From<CalendarDate> for lv_calendar_date_t {
+    fn from(date: CalendarDate) -> Self {
+        Self {
+            year: date.year,
+            month: date.month as i8,
+            day: date.day as i8,
+        }
+    }
+}
+
+impl From<lv_calendar_date_t> for CalendarDate {
+    fn from(date: lv_calendar_date_t) -> Self {
+        Self::new(date.year, date.month as u8, date.day as u8)
+    }
+}
+
+/// Fails on years out of the range of `u16`, e.g. before Christ
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::NaiveDate> for CalendarDate {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(date: chrono::NaiveDate) -> Result<Self, Self::Error> {
+        use chrono::Datelike;
+        let year = u16::try_from(date.year())?;
+        Ok(Self::new(year, date.month() as u8, date.day() as u8))
+    }
+}
+
+/// Fails on dates that don't exist, e.g. the 31st of April
+#[cfg(feature = "chrono")]
+impl TryFrom<CalendarDate> for chrono::NaiveDate {
+    type Error = ();
+
+    fn try_from(date: CalendarDate) -> Result<Self, Self::Error> {
+        chrono::NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+            .ok_or(())
+    }
+}
+
+/// The highlighted dates, to which LVGL only points
+#[derive(Default)]
+struct HighlightedDates(Vec<lv_calendar_date_t>);
+
+impl<C: 'static> Calendar<C> {
+    pub fn set_today(&mut self, date: CalendarDate) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_calendar_set_today_date(
+                &mut *self.raw,
+                date.year.into(),
+                date.month.into(),
+                date.day.into(),
+            )
+        };
+        self
+    }
+
+    pub fn today(&self) -> CalendarDate {
+        unsafe { *lvgl_sys::lv_calendar_get_today_date(&*self.raw) }.into()
+    }
+
+    /// Show the month of `date`, its day is ignored
+    pub fn set_showed_date(&mut self, date: CalendarDate) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_calendar_set_showed_date(
+                &mut *self.raw,
+                date.year.into(),
+                date.month.into(),
+            )
+        };
+        self
+    }
+
+    /// The first day of the shown month
+    pub fn showed_date(&self) -> CalendarDate {
+        unsafe { *lvgl_sys::lv_calendar_get_showed_date(&*self.raw) }.into()
+    }
+
+    /// Highlight these dates, replacing the previous ones. They are kept with the calendar.
+    pub fn set_highlighted_dates(
+        &mut self,
+        dates: impl IntoIterator<Item = CalendarDate>,
+    ) -> &mut Self {
+        let dates: Vec<_> = dates.into_iter().map(Into::into).collect();
+        let num = u16::try_from(dates.len()).expect("Too many highlighted dates");
+        let highlighted = attached::<HighlightedDates>(&mut *self.raw);
+        highlighted.0 = dates;
+        unsafe {
+            lvgl_sys::lv_calendar_set_highlighted_dates(
+                &mut *self.raw,
+                highlighted.0.as_mut_ptr(),
+                num,
+            )
+        };
+        self
+    }
+
+    pub fn highlighted_dates(&self) -> Vec<CalendarDate> {
+        let dates = unsafe { lvgl_sys::lv_calendar_get_highlighted_dates(&*self.raw) };
+        if dates.is_null() {
+            return Vec::new();
+        }
+        let num = unsafe { lvgl_sys::lv_calendar_get_highlighted_dates_num(&*self.raw) };
+        let dates = unsafe { core::slice::from_raw_parts(dates, num as usize) };
+        dates.iter().map(|date| (*date).into()).collect()
+    }
+
+    /// The date being pressed, or the last one clicked
+    pub fn pressed_date(&self) -> Option<CalendarDate> {
+        pressed_date(&*self.raw)
+    }
+
+    /// Register a callback for when a date of the shown month is clicked, or selected with the
+    /// keys
+    pub fn on_date_clicked(
+        &mut self,
+        mut f: impl FnMut(&mut C, CalendarDate) + 'static,
+    ) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, target| {
+                // Only the days bubble up, not the headers
+                let btnm = unsafe { lvgl_sys::lv_calendar_get_btnmatrix(current_target) };
+                if btnm != target as *mut lv_obj_t {
+                    return;
+                }
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                if let Some(date) = pressed_date(current_target) {
+                    f(context, date)
+                }
+            },
+        );
+        self
+    }
+}
+
+fn pressed_date(calendar: &lv_obj_t) -> Option<CalendarDate> {
+    let mut date = lv_calendar_date_t {
+        year: 0,
+        month: 0,
+        day: 0,
+    };
+    let res = unsafe { lvgl_sys::lv_calendar_get_pressed_date(calendar, &mut date) };
+    (res == lvgl_sys::LV_RES_OK as lvgl_sys::lv_res_t).then(|| date.into())
+}
+
+define_object!(
+    /// A header with the shown month and arrows to go to the previous and next months
+    #[cfg(lv_use_calendar_header_arrow)]
+    CalendarHeaderArrow
+);
+
+#[cfg(lv_use_calendar_header_arrow)]
+impl<C: 'static> CalendarHeaderArrow<C> {
+    /// Add the header on top of `calendar`
+    pub fn new(calendar: &mut Calendar<C>) -> Self {
+        unsafe {
+            let obj = lvgl_sys::lv_calendar_header_arrow_create(&mut *calendar.raw);
+            let obj = Obj::from_raw(obj.as_mut().expect("OOM"), calendar.context);
+            Self { obj }
+        }
+    }
+}
+
+define_object!(
+    /// A header with dropdowns to pick the shown year and month
+    #[cfg(lv_use_calendar_header_dropdown)]
+    CalendarHeaderDropdown
+);
+
+#[cfg(lv_use_calendar_header_dropdown)]
+impl<C: 'static> CalendarHeaderDropdown<C> {
+    /// Add the header on top of `calendar`
+    pub fn new(calendar: &mut Calendar<C>) -> Self {
+        unsafe {
+            let obj = lvgl_sys::lv_calendar_header_dropdown_create(&mut *calendar.raw);
+            let obj = Obj::from_raw(obj.as_mut().expect("OOM"), calendar.context);
+            Self { obj }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use alloc::vec::Vec;
+use core::convert::{Infallible, TryFrom};
+use cstr_core::CStr;
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+use lvgl_sys::{lv_area_t, lv_coord_t, lv_obj_t, lv_point_t};
+
+use crate::core::{attach, from_lv_color, to_lv_color, with_cstr, ObjExt, PixelColor};
+use crate::draw::{ArcDsc, ImgDsc, LabelDsc, LineDsc, RectDsc};
+use crate::image::{ColorFormat, ImageDsc, ImageSource};
+use crate::style::Opacity;
+use crate::widgets::Canvas;
+
+impl<C: 'static> Canvas<C> {
+    /// A canvas with its own buffer of `width * height` pixels, freed with the canvas
+    pub fn with_size(parent: &mut impl ObjExt<C>, width: u16, height: u16) -> Self {
+        let mut canvas = Self::new(parent);
+        let buffer: Vec<PixelColor> = core::iter::repeat(PixelColor::default())
+            .take(width as usize * height as usize)
+            .collect();
+        let buffer = attach(&mut *canvas.raw, buffer);
+        canvas.set_buffer(buffer.as_mut_slice(), width, height);
+        canvas
+    }
+
+    /// Draw into `buffer`, of at least `width * height` pixels. LVGL keeps a pointer to it,
+    /// hence the `'static`.
+    pub fn set_buffer(
+        &mut self,
+        buffer: &'static mut [PixelColor],
+        width: u16,
+        height: u16,
+    ) -> &mut Self {
+        assert!(
+            buffer.len() >= width as usize * height as usize,
+            "The buffer is too small for the canvas"
+        );
+        unsafe {
+            lvgl_sys::lv_canvas_set_buffer(
+                &mut *self.raw,
+                buffer.as_mut_ptr() as *mut cty::c_void,
+                width as lv_coord_t,
+                height as lv_coord_t,
+                ColorFormat::TrueColor.into(),
+            )
+        };
+        self
+    }
+
+    /// The image drawn so far, e.g. to transform it into another canvas
+    pub fn image(&self) -> &ImageDsc {
+        // ImageDsc is a transparent wrapper
+        unsafe { &*(lvgl_sys::lv_canvas_get_img(self.raw_ptr()) as *const ImageDsc) }
+    }
+
+    /// Does nothing outside of the buffer, or without one
+    pub fn set_px(&mut self, x: lv_coord_t, y: lv_coord_t, color: PixelColor) -> &mut Self {
+        if self.contains(x, y) {
+            unsafe { lvgl_sys::lv_canvas_set_px_color(&mut *self.raw, x, y, to_lv_color(color)) };
+        }
+        self
+    }
+
+    /// `None` outside of the buffer, or without one
+    pub fn get_px(&self, x: lv_coord_t, y: lv_coord_t) -> Option<PixelColor> {
+        if self.contains(x, y) {
+            Some(from_lv_color(unsafe {
+                lvgl_sys::lv_canvas_get_px(self.raw_ptr(), x, y)
+            }))
+        } else {
+            None
+        }
+    }
+
+    pub fn fill_bg(&mut self, color: PixelColor, opa: Opacity) -> &mut Self {
+        unsafe { lvgl_sys::lv_canvas_fill_bg(&mut *self.raw, to_lv_color(color), opa.into()) };
+        self
+    }
+
+    pub fn draw_rect(&mut self, area: &Rectangle, dsc: &RectDsc) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_canvas_draw_rect(
+                &mut *self.raw,
+                area.top_left.x as lv_coord_t,
+                area.top_left.y as lv_coord_t,
+                area.size.width as lv_coord_t,
+                area.size.height as lv_coord_t,
+                dsc.raw(),
+            )
+        };
+        self
+    }
+
+    /// Draw `text` at `(x, y)`, wrapped at `max_w` pixels
+    pub fn draw_text(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        max_w: lv_coord_t,
+        dsc: &LabelDsc,
+        text: impl AsRef<str>,
+    ) -> &mut Self {
+        with_cstr(text.as_ref(), |text| {
+            self.draw_text_ptr(x, y, max_w, dsc, text)
+        });
+        self
+    }
+
+    pub fn draw_text_cstr(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        max_w: lv_coord_t,
+        dsc: &LabelDsc,
+        text: &CStr,
+    ) -> &mut Self {
+        self.draw_text_ptr(x, y, max_w, dsc, text.as_ptr());
+        self
+    }
+
+    /// Draw a line through all the `points`
+    pub fn draw_line(&mut self, points: &[Point], dsc: &LineDsc) -> &mut Self {
+        let points = lv_points(points);
+        unsafe {
+            lvgl_sys::lv_canvas_draw_line(
+                &mut *self.raw,
+                points.as_ptr(),
+                points.len() as u32,
+                dsc.raw(),
+            )
+        };
+        self
+    }
+
+    pub fn draw_polygon(&mut self, points: &[Point], dsc: &RectDsc) -> &mut Self {
+        let points = lv_points(points);
+        unsafe {
+            lvgl_sys::lv_canvas_draw_polygon(
+                &mut *self.raw,
+                points.as_ptr(),
+                points.len() as u32,
+                dsc.raw(),
+            )
+        };
+        self
+    }
+
+    /// Draw an arc from `start_angle` to `end_angle`, in degrees clockwise from the right
+    pub fn draw_arc(
+        &mut self,
+        center: Point,
+        radius: lv_coord_t,
+        start_angle: i32,
+        end_angle: i32,
+        dsc: &ArcDsc,
+    ) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_canvas_draw_arc(
+                &mut *self.raw,
+                center.x as lv_coord_t,
+                center.y as lv_coord_t,
+                radius,
+                start_angle,
+                end_angle,
+                dsc.raw(),
+            )
+        };
+        self
+    }
+
+    /// Draw an image. It is drawn right away, so it doesn't need to outlive the call.
+    pub fn draw_img(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        src: ImageSource,
+        dsc: &ImgDsc,
+    ) -> &mut Self {
+        unsafe { lvgl_sys::lv_canvas_draw_img(&mut *self.raw, x, y, src.as_ptr(), dsc.raw()) };
+        self
+    }
+
+    /// Draw `img` rotated by `angle` (in 0.1 degrees) and zoomed by `zoom` (256 is the original
+    /// size) around `pivot`, at `offset`. `img` must not be the image of this canvas: use a copy.
+    pub fn transform(
+        &mut self,
+        img: &ImageDsc,
+        angle: i16,
+        zoom: u16,
+        offset: Point,
+        pivot: Point,
+        antialias: bool,
+    ) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_canvas_transform(
+                &mut *self.raw,
+                // Only read by LVGL
+                img.raw() as *const _ as *mut _,
+                angle,
+                zoom,
+                offset.x as lv_coord_t,
+                offset.y as lv_coord_t,
+                pivot.x,
+                pivot.y,
+                antialias,
+            )
+        };
+        self
+    }
+
+    /// Blur an area, horizontally then vertically, with a radius of `r` pixels
+    pub fn blur(&mut self, area: &Rectangle, r: u16) -> &mut Self {
+        let area = lv_area(area);
+        unsafe {
+            lvgl_sys::lv_canvas_blur_hor(&mut *self.raw, &area, r);
+            lvgl_sys::lv_canvas_blur_ver(&mut *self.raw, &area, r);
+        }
+        self
+    }
+
+    fn draw_text_ptr(
+        &mut self,
+        x: lv_coord_t,
+        y: lv_coord_t,
+        max_w: lv_coord_t,
+        dsc: &LabelDsc,
+        text: *const cty::c_char,
+    ) {
+        // LVGL wants a mutable descriptor, but only to read it
+        let mut dsc = dsc.clone();
+        unsafe { lvgl_sys::lv_canvas_draw_text(&mut *self.raw, x, y, max_w, dsc.raw_mut(), text) };
+    }
+
+    // LVGL doesn't check the coordinates of a pixel
+    fn contains(&self, x: lv_coord_t, y: lv_coord_t) -> bool {
+        let img = self.image();
+        !img.raw().data.is_null()
+            && x >= 0
+            && y >= 0
+            && (x as u32) < img.width() as u32
+            && (y as u32) < img.height() as u32
+    }
+
+    // LVGL takes a mutable canvas even to read it
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+fn lv_points(points: &[Point]) -> Vec<lv_point_t> {
+    points
+        .iter()
+        .map(|p| lv_point_t {
+            x: p.x as lv_coord_t,
+            y: p.y as lv_coord_t,
+        })
+        .collect()
+}
+
+fn lv_area(area: &Rectangle) -> lv_area_t {
+    lv_area_t {
+        x1: area.top_left.x as lv_coord_t,
+        y1: area.top_left.y as lv_coord_t,
+        x2: (area.top_left.x + area.size.width as i32 - 1) as lv_coord_t,
+        y2: (area.top_left.y + area.size.height as i32 - 1) as lv_coord_t,
+    }
+}
+
+/// Draw on the canvas with `embedded_graphics`
+impl<C: 'static> DrawTarget for Canvas<C> {
+    type Color = PixelColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            // Out of range coordinates are dropped by set_px
+            if let (Ok(x), Ok(y)) = (lv_coord_t::try_from(point.x), lv_coord_t::try_from(point.y)) {
+                self.set_px(x, y, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let mut dsc = RectDsc::default();
+        dsc.set_bg_color(color);
+        self.draw_rect(area, &dsc);
+        Ok(())
+    }
+}
+
+impl<C: 'static> OriginDimensions for Canvas<C> {
+    fn size(&self) -> Size {
+        let img = self.image();
+        Size::new(img.width() as u32, img.height() as u32)
+    }
+}
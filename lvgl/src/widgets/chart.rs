@@ -0,0 +1,333 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ptr::NonNull;
+use core::slice;
+use lvgl_sys::{lv_chart_cursor_t, lv_chart_series_t, lv_coord_t, lv_obj_t};
+
+use crate::core::{attached, to_lv_color, PixelColor};
+use crate::style::Dir;
+use crate::widgets::{Chart, ObjId};
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_type_t,
+    pub enum ChartType {
+        /// Don't draw the series
+        None = lvgl_sys::LV_CHART_TYPE_NONE,
+        /// Connect the points with lines
+        Line = lvgl_sys::LV_CHART_TYPE_LINE,
+        /// Draw columns
+        Bar = lvgl_sys::LV_CHART_TYPE_BAR,
+        /// Draw points and lines in 2D, with x and y coordinates
+        Scatter = lvgl_sys::LV_CHART_TYPE_SCATTER,
+    }
+}
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_update_mode_t,
+    /// How `Chart::push()` adds a value
+    pub enum ChartUpdateMode {
+        /// Shift the old values to the left and add the new one on the right
+        Shift = lvgl_sys::LV_CHART_UPDATE_MODE_SHIFT,
+        /// Add the new value in a circular way
+        Circular = lvgl_sys::LV_CHART_UPDATE_MODE_CIRCULAR,
+    }
+}
+
+crate::native_enum! {
+    lvgl_sys::lv_chart_axis_t,
+    pub enum ChartAxis {
+        PrimaryY = lvgl_sys::LV_CHART_AXIS_PRIMARY_Y,
+        SecondaryY = lvgl_sys::LV_CHART_AXIS_SECONDARY_Y,
+        PrimaryX = lvgl_sys::LV_CHART_AXIS_PRIMARY_X,
+        SecondaryX = lvgl_sys::LV_CHART_AXIS_SECONDARY_X,
+    }
+}
+
+impl<C: 'static> Chart<C> {
+    /// Add a series of points. Its values are `LV_CHART_POINT_NONE` until set.
+    pub fn add_series(&mut self, color: PixelColor, axis: ChartAxis) -> ChartSeries {
+        let raw = unsafe {
+            lvgl_sys::lv_chart_add_series(&mut *self.raw, to_lv_color(color), axis.into())
+        };
+        ChartSeries {
+            chart: ObjId::of(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    pub fn remove_series(&mut self, series: ChartSeries) {
+        let series = self.series_ptr(&series);
+        attached::<ExtArrays>(&mut *self.raw).remove(series);
+        unsafe { lvgl_sys::lv_chart_remove_series(&mut *self.raw, series) };
+    }
+
+    /// Set the number of points of all the series, at most the length of their external arrays
+    pub fn set_point_count(&mut self, count: u16) -> &mut Self {
+        assert!(
+            attached::<ExtArrays>(&mut *self.raw).fit(count),
+            "More points than values in an external array"
+        );
+        unsafe { lvgl_sys::lv_chart_set_point_count(&mut *self.raw, count) };
+        self
+    }
+
+    /// The y values of a series, one per point. `Chart::push()` writes them as a ring buffer
+    /// starting at `Chart::x_start_point()`, whatever the update mode.
+    pub fn values(&self, series: &ChartSeries) -> &[lv_coord_t] {
+        let series = self.series_ptr(series);
+        unsafe {
+            let values = lvgl_sys::lv_chart_get_y_array(self.raw_ptr(), series);
+            slice::from_raw_parts(values, self.point_count() as usize)
+        }
+    }
+
+    /// Call `Chart::refresh()` after changing the values
+    pub fn values_mut(&mut self, series: &mut ChartSeries) -> &mut [lv_coord_t] {
+        let series = self.series_ptr(series);
+        unsafe {
+            let values = lvgl_sys::lv_chart_get_y_array(&mut *self.raw, series);
+            slice::from_raw_parts_mut(values, self.point_count() as usize)
+        }
+    }
+
+    /// Copy `values` into the first points of a series
+    pub fn set_values(&mut self, series: &mut ChartSeries, values: &[lv_coord_t]) -> &mut Self {
+        let points = self.values_mut(series);
+        let len = values.len().min(points.len());
+        points[..len].copy_from_slice(&values[..len]);
+        unsafe { lvgl_sys::lv_chart_refresh(&mut *self.raw) };
+        self
+    }
+
+    /// Add a value to a series, as set by `Chart::set_update_mode()`
+    pub fn push(&mut self, series: &mut ChartSeries, value: lv_coord_t) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_next_value(&mut *self.raw, series, value) };
+        self
+    }
+
+    /// Set all the points of a series to `value`
+    pub fn set_all(&mut self, series: &mut ChartSeries, value: lv_coord_t) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_all_value(&mut *self.raw, series, value) };
+        self
+    }
+
+    pub fn set_value(&mut self, series: &mut ChartSeries, id: u16, value: lv_coord_t) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_value_by_id(&mut *self.raw, series, id, value) };
+        self
+    }
+
+    /// Plot `values` instead of the points of a series. LVGL keeps a pointer to it, hence the
+    /// `'static`. It must hold at least `Chart::point_count()` values, which
+    /// `Chart::set_point_count()` can't exceed afterwards.
+    pub fn set_ext_y_array(
+        &mut self,
+        series: &mut ChartSeries,
+        values: &'static mut [lv_coord_t],
+    ) -> &mut Self {
+        let series = self.ext_array_series(series, Axis::Y, values);
+        unsafe { lvgl_sys::lv_chart_set_ext_y_array(&mut *self.raw, series, values.as_mut_ptr()) };
+        self
+    }
+
+    /// Like `set_ext_y_array()`, for the x values of scatter charts
+    pub fn set_ext_x_array(
+        &mut self,
+        series: &mut ChartSeries,
+        values: &'static mut [lv_coord_t],
+    ) -> &mut Self {
+        let series = self.ext_array_series(series, Axis::X, values);
+        unsafe { lvgl_sys::lv_chart_set_ext_x_array(&mut *self.raw, series, values.as_mut_ptr()) };
+        self
+    }
+
+    pub fn set_series_color(&mut self, series: &mut ChartSeries, color: PixelColor) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_series_color(&mut *self.raw, series, to_lv_color(color)) };
+        self
+    }
+
+    pub fn set_series_hidden(&mut self, series: &mut ChartSeries, hidden: bool) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_hide_series(&mut *self.raw, series, hidden) };
+        self
+    }
+
+    /// The index of the point of a series drawn first, the oldest value in
+    /// `ChartUpdateMode::Shift`
+    pub fn x_start_point(&self, series: &ChartSeries) -> u16 {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_get_x_start_point(self.raw_ptr(), series) }
+    }
+
+    pub fn set_x_start_point(&mut self, series: &mut ChartSeries, id: u16) -> &mut Self {
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_x_start_point(&mut *self.raw, series, id) };
+        self
+    }
+
+    /// Add a cursor, drawing lines from its position in the `dir` directions
+    pub fn add_cursor(&mut self, color: PixelColor, dir: Dir) -> ChartCursor {
+        let raw = unsafe {
+            lvgl_sys::lv_chart_add_cursor(&mut *self.raw, to_lv_color(color), dir.bits())
+        };
+        ChartCursor {
+            chart: ObjId::of(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    /// Move a cursor to a position, relative to the chart
+    pub fn set_cursor_pos(
+        &mut self,
+        cursor: &mut ChartCursor,
+        x: lv_coord_t,
+        y: lv_coord_t,
+    ) -> &mut Self {
+        let cursor = self.cursor_ptr(cursor);
+        let mut pos = lvgl_sys::lv_point_t { x, y };
+        unsafe { lvgl_sys::lv_chart_set_cursor_pos(&mut *self.raw, cursor, &mut pos) };
+        self
+    }
+
+    /// Stick a cursor to a point of a series
+    pub fn set_cursor_point(
+        &mut self,
+        cursor: &mut ChartCursor,
+        series: &ChartSeries,
+        id: u16,
+    ) -> &mut Self {
+        let cursor = self.cursor_ptr(cursor);
+        let series = self.series_ptr(series);
+        unsafe { lvgl_sys::lv_chart_set_cursor_point(&mut *self.raw, cursor, series, id) };
+        self
+    }
+
+    /// The position of a cursor, relative to the chart
+    pub fn cursor_pos(&self, cursor: &ChartCursor) -> (lv_coord_t, lv_coord_t) {
+        let cursor = self.cursor_ptr(cursor);
+        let pos = unsafe { lvgl_sys::lv_chart_get_cursor_point(self.raw_ptr(), cursor) };
+        (pos.x, pos.y)
+    }
+
+    pub fn chart_type(&self) -> ChartType {
+        ChartType::try_from(unsafe { lvgl_sys::lv_chart_get_type(&*self.raw) }).unwrap()
+    }
+
+    pub fn point_count(&self) -> u16 {
+        unsafe { lvgl_sys::lv_chart_get_point_count(&*self.raw) }
+    }
+
+    /// 256 is no zoom
+    pub fn zoom_x(&self) -> u16 {
+        unsafe { lvgl_sys::lv_chart_get_zoom_x(&*self.raw) }
+    }
+
+    /// 256 is no zoom
+    pub fn zoom_y(&self) -> u16 {
+        unsafe { lvgl_sys::lv_chart_get_zoom_y(&*self.raw) }
+    }
+
+    /// The index of the last pressed point, e.g. in a `ValueChanged` callback
+    pub fn pressed_point(&self) -> Option<u16> {
+        let id = unsafe { lvgl_sys::lv_chart_get_pressed_point(&*self.raw) };
+        if id == lvgl_sys::LV_CHART_POINT_NONE as u32 {
+            None
+        } else {
+            Some(id as u16)
+        }
+    }
+
+    fn ext_array_series(
+        &mut self,
+        series: &ChartSeries,
+        axis: Axis,
+        values: &[lv_coord_t],
+    ) -> *mut lv_chart_series_t {
+        let series = self.series_ptr(series);
+        assert!(
+            values.len() >= self.point_count() as usize,
+            "Fewer values than points"
+        );
+        attached::<ExtArrays>(&mut *self.raw).insert(series, axis, values.len());
+        series
+    }
+
+    fn series_ptr(&self, series: &ChartSeries) -> *mut lv_chart_series_t {
+        assert!(
+            series.chart == ObjId::of(self.raw_ptr()),
+            "The series is not one of this chart"
+        );
+        series.raw.as_ptr()
+    }
+
+    fn cursor_ptr(&self, cursor: &ChartCursor) -> *mut lv_chart_cursor_t {
+        assert!(
+            cursor.chart == ObjId::of(self.raw_ptr()),
+            "The cursor is not one of this chart"
+        );
+        cursor.raw.as_ptr()
+    }
+
+    // LVGL takes a mutable chart even to read its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+/// The lengths of the external arrays of the series of a chart, by series, which bound the
+/// number of points
+#[derive(Default)]
+struct ExtArrays(Vec<(*mut lv_chart_series_t, Axis, usize)>);
+
+#[derive(PartialEq)]
+enum Axis {
+    X,
+    Y,
+}
+
+impl ExtArrays {
+    fn insert(&mut self, series: *mut lv_chart_series_t, axis: Axis, len: usize) {
+        self.0.retain(|(s, a, _)| *s != series || *a != axis);
+        self.0.push((series, axis, len));
+    }
+
+    fn remove(&mut self, series: *mut lv_chart_series_t) {
+        self.0.retain(|(s, _, _)| *s != series);
+    }
+
+    fn fit(&self, point_count: u16) -> bool {
+        self.0
+            .iter()
+            .all(|(_, _, len)| *len >= point_count as usize)
+    }
+}
+
+/// A series of points of a chart, see `Chart::add_series()`. It is only used through its
+/// chart, e.g. `Chart::push()`, until `Chart::remove_series()` consumes it. It can't be cloned,
+/// so it can't outlive its removal.
+pub struct ChartSeries {
+    chart: ObjId,
+    raw: NonNull<lv_chart_series_t>,
+}
+
+impl ChartSeries {
+    pub fn raw(&mut self) -> *mut lv_chart_series_t {
+        self.raw.as_ptr()
+    }
+}
+
+/// A cursor of a chart, see `Chart::add_cursor()`. It is only used through its chart, e.g.
+/// `Chart::set_cursor_pos()`.
+pub struct ChartCursor {
+    chart: ObjId,
+    raw: NonNull<lv_chart_cursor_t>,
+}
+
+impl ChartCursor {
+    pub fn raw(&mut self) -> *mut lv_chart_cursor_t {
+        self.raw.as_ptr()
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::string::{String, ToString};
+use core::convert::TryFrom;
+
+use crate::core::{add_event_cb, join_lines, to_str, with_cstr, Event};
+use crate::image::ImageSource;
+use crate::style::Dir;
+use crate::widgets::Dropdown;
+
+impl<C: 'static> Dropdown<C> {
+    /// Set the options, one per item. LVGL copies them.
+    pub fn set_options<T: AsRef<str>>(
+        &mut self,
+        options: impl IntoIterator<Item = T>,
+    ) -> &mut Self {
+        let options = join_lines(options);
+        with_cstr(&options, |options| unsafe {
+            lvgl_sys::lv_dropdown_set_options(&mut *self.raw, options)
+        });
+        self
+    }
+
+    pub fn options(&self) -> impl Iterator<Item = &str> {
+        let options = to_str(unsafe { lvgl_sys::lv_dropdown_get_options(&*self.raw) });
+        options.split('\n').take(self.option_count())
+    }
+
+    pub fn option_count(&self) -> usize {
+        unsafe { lvgl_sys::lv_dropdown_get_option_cnt(&*self.raw) as usize }
+    }
+
+    /// Select an option, the last one if `index` is past the end
+    pub fn set_selected(&mut self, index: usize) -> &mut Self {
+        let index = u16::try_from(index).unwrap_or(u16::MAX);
+        unsafe { lvgl_sys::lv_dropdown_set_selected(&mut *self.raw, index) };
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        unsafe { lvgl_sys::lv_dropdown_get_selected(&*self.raw) as usize }
+    }
+
+    pub fn selected_str(&self) -> String {
+        self.options()
+            .nth(self.selected())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Where the list opens, see `set_dir()`
+    pub fn dir(&self) -> Dir {
+        Dir::from_bits_truncate(unsafe { lvgl_sys::lv_dropdown_get_dir(&*self.raw) })
+    }
+
+    /// Show a symbol or an image on the side of the dropdown, usually an arrow pointing to where
+    /// the list opens. LVGL keeps a pointer to the source, hence the `'static`.
+    pub fn set_symbol(&mut self, symbol: Option<ImageSource<'static>>) -> &mut Self {
+        unsafe { lvgl_sys::lv_dropdown_set_symbol(&mut *self.raw, ImageSource::opt_ptr(symbol)) };
+        self
+    }
+
+    /// Register a callback for when an option is selected from the list, receiving its index
+    pub fn on_selected(&mut self, mut f: impl FnMut(&mut C, usize) + 'static) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, _child| {
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                let selected = unsafe { lvgl_sys::lv_dropdown_get_selected(current_target) };
+                f(context, selected as usize)
+            },
+        );
+        self
+    }
+}
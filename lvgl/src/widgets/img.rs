@@ -0,0 +1,10 @@
+use crate::image::ImageSource;
+use crate::widgets::Img;
+
+impl<C: 'static> Img<C> {
+    /// Set the image to show. Paths and symbols are copied by LVGL.
+    pub fn set_src(&mut self, src: ImageSource) -> &mut Self {
+        unsafe { lvgl_sys::lv_img_set_src(&mut *self.raw, src.as_ptr()) };
+        self
+    }
+}
@@ -0,0 +1,26 @@
+use crate::image::ImageSource;
+use crate::widgets::Imgbtn;
+
+impl<C: 'static> Imgbtn<C> {
+    /// Set the images of a state. The middle image is repeated to fill the width of the
+    /// button, between the optional left and right images. The button keeps pointers to the
+    /// sources, hence the `'static`.
+    pub fn set_src(
+        &mut self,
+        state: lvgl_sys::lv_imgbtn_state_t,
+        left: Option<ImageSource<'static>>,
+        mid: ImageSource<'static>,
+        right: Option<ImageSource<'static>>,
+    ) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_imgbtn_set_src(
+                &mut *self.raw,
+                state,
+                ImageSource::opt_ptr(left),
+                mid.as_ptr(),
+                ImageSource::opt_ptr(right),
+            )
+        };
+        self
+    }
+}
@@ -0,0 +1,190 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::convert::TryFrom;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use lvgl_sys::lv_obj_t;
+
+use crate::core::{attached, Event, ObjExt};
+use crate::widgets::{BtnMap, BtnmatrixCtrl, Keyboard, Textarea};
+
+crate::native_enum! {
+    lvgl_sys::lv_keyboard_mode_t,
+    /// The layouts of a keyboard. The `User*` modes show the lowercase layout until they are set
+    /// with `Keyboard::set_map()`.
+    pub enum KeyboardMode {
+        TextLower = lvgl_sys::LV_KEYBOARD_MODE_TEXT_LOWER,
+        TextUpper = lvgl_sys::LV_KEYBOARD_MODE_TEXT_UPPER,
+        Special = lvgl_sys::LV_KEYBOARD_MODE_SPECIAL,
+        Number = lvgl_sys::LV_KEYBOARD_MODE_NUMBER,
+        User1 = lvgl_sys::LV_KEYBOARD_MODE_USER_1,
+        User2 = lvgl_sys::LV_KEYBOARD_MODE_USER_2,
+        User3 = lvgl_sys::LV_KEYBOARD_MODE_USER_3,
+        User4 = lvgl_sys::LV_KEYBOARD_MODE_USER_4,
+    }
+}
+
+/// Texts of the keys the keyboard acts on instead of typing them
+pub mod keys {
+    /// Sends `Ready`
+    pub const OK: &str = "\u{F00C}";
+    /// Sends `Cancel`
+    pub const CLOSE: &str = "\u{F00D}";
+    /// Sends `Cancel`
+    pub const KEYBOARD: &str = "\u{F11C}";
+    pub const BACKSPACE: &str = "\u{F55A}";
+    pub const LEFT: &str = "\u{F053}";
+    pub const RIGHT: &str = "\u{F054}";
+    /// Types a new line, or sends `Ready` to one-line text areas
+    pub const NEW_LINE: &str = "\u{F8A2}";
+    /// Shows the `TextLower` layout
+    pub const LOWER: &str = "abc";
+    /// Shows the `TextUpper` layout
+    pub const UPPER: &str = "ABC";
+    /// Shows the `Special` layout
+    pub const SPECIAL: &str = "1#";
+}
+
+impl<C: 'static> Keyboard<C> {
+    /// Set the layout of a mode, row by row, with the controls of its buttons in the same order.
+    /// LVGL shares the layouts between all the keyboards, so a layout is kept until the layout of
+    /// its mode is set again.
+    pub fn set_map(
+        &mut self,
+        mode: KeyboardMode,
+        rows: &[&[&str]],
+        ctrl_map: &[BtnmatrixCtrl],
+    ) -> &mut Self {
+        let map = BtnMap::new(rows);
+        assert_eq!(ctrl_map.len(), map.btn_count(), "One control per button");
+        let ctrl_map = ctrl_map.iter().map(|ctrl| ctrl.bits()).collect();
+        let layout = Box::into_raw(Box::new(Layout { map, ctrl_map }));
+        let mode: lvgl_sys::lv_keyboard_mode_t = mode.into();
+        unsafe {
+            lvgl_sys::lv_keyboard_set_map(
+                &mut *self.raw,
+                mode,
+                (*layout).map.as_ptr(),
+                (*layout).ctrl_map.as_ptr(),
+            );
+            // Only this keyboard is updated, the others still show the former layout
+            refresh_keyboards(mode, layout);
+        }
+
+        let former = LAYOUTS[mode as usize].swap(layout, Ordering::AcqRel);
+        if !former.is_null() {
+            drop(unsafe { Box::from_raw(former) });
+        }
+        self
+    }
+
+    pub fn mode(&self) -> KeyboardMode {
+        KeyboardMode::try_from(unsafe { lvgl_sys::lv_keyboard_get_mode(&*self.raw) }).unwrap()
+    }
+
+    /// Type into `textarea`. The keyboard forgets it when it is deleted.
+    pub fn set_textarea(&mut self, textarea: &Textarea<C>) -> &mut Self {
+        // Only to keep track of the keyboards typing into it
+        let ta = &*textarea.raw as *const lv_obj_t as *mut lv_obj_t;
+        let kb = &mut *self.raw as *mut lv_obj_t;
+        unsafe { lvgl_sys::lv_keyboard_set_textarea(kb, ta) };
+
+        let keyboards = attached::<Keyboards>(unsafe { &mut *ta });
+        keyboards.textarea = ta;
+        if !keyboards.keyboards.contains(&kb) {
+            keyboards.keyboards.push(kb);
+        }
+        self
+    }
+
+    pub fn clear_textarea(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_keyboard_set_textarea(&mut *self.raw, ptr::null_mut()) };
+        self
+    }
+
+    pub fn has_textarea(&self) -> bool {
+        !unsafe { lvgl_sys::lv_keyboard_get_textarea(&*self.raw) }.is_null()
+    }
+
+    /// Register a callback for when `keys::OK` is pressed
+    pub fn on_ready(&mut self, f: impl FnMut(&mut C) + 'static) -> &mut Self {
+        self.on_event(Event::Ready, f)
+    }
+
+    /// Register a callback for when `keys::CLOSE` or `keys::KEYBOARD` is pressed
+    pub fn on_cancel(&mut self, f: impl FnMut(&mut C) + 'static) -> &mut Self {
+        self.on_event(Event::Cancel, f)
+    }
+}
+
+/// A layout set with `Keyboard::set_map()`
+struct Layout {
+    map: BtnMap,
+    ctrl_map: Vec<lvgl_sys::lv_btnmatrix_ctrl_t>,
+}
+
+// The layouts of the modes, indexed by `lv_keyboard_mode_t`, as LVGL keeps one per mode
+const NO_LAYOUT: AtomicPtr<Layout> = AtomicPtr::new(ptr::null_mut());
+static LAYOUTS: [AtomicPtr<Layout>; 8] = [NO_LAYOUT; 8];
+
+/// Shows `layout` on all the keyboards in `mode`
+unsafe fn refresh_keyboards(mode: lvgl_sys::lv_keyboard_mode_t, layout: *mut Layout) {
+    unsafe extern "C" fn refresh(
+        obj: *mut lv_obj_t,
+        user_data: *mut cty::c_void,
+    ) -> lvgl_sys::lv_obj_tree_walk_res_t {
+        let (mode, layout) = *(user_data as *const (lvgl_sys::lv_keyboard_mode_t, *mut Layout));
+        if lvgl_sys::lv_obj_check_type(obj, &lvgl_sys::lv_keyboard_class)
+            && lvgl_sys::lv_keyboard_get_mode(obj) == mode
+        {
+            // Setting the mode again is a no-op, setting the map updates the keyboard
+            let layout = &mut *layout;
+            lvgl_sys::lv_keyboard_set_map(obj, mode, layout.map.as_ptr(), layout.ctrl_map.as_ptr());
+        }
+        lvgl_sys::LV_OBJ_TREE_WALK_NEXT as lvgl_sys::lv_obj_tree_walk_res_t
+    }
+
+    let mut user_data = (mode, layout);
+    let user_data = &mut user_data as *mut _ as *mut cty::c_void;
+    let mut disp = lvgl_sys::lv_disp_get_next(ptr::null_mut());
+    while let Some(d) = disp.as_ref() {
+        for i in 0..d.screen_cnt {
+            lvgl_sys::lv_obj_tree_walk(*d.screens.add(i as usize), Some(refresh), user_data);
+        }
+        lvgl_sys::lv_obj_tree_walk(d.top_layer, Some(refresh), user_data);
+        lvgl_sys::lv_obj_tree_walk(d.sys_layer, Some(refresh), user_data);
+        disp = lvgl_sys::lv_disp_get_next(disp);
+    }
+}
+
+/// The keyboards given a text area. They forget it when it is deleted, as LVGL would keep
+/// typing into the deleted text area.
+struct Keyboards {
+    textarea: *mut lv_obj_t,
+    keyboards: Vec<*mut lv_obj_t>,
+}
+
+impl Default for Keyboards {
+    fn default() -> Self {
+        Self {
+            textarea: ptr::null_mut(),
+            keyboards: Vec::new(),
+        }
+    }
+}
+
+impl Drop for Keyboards {
+    fn drop(&mut self) {
+        for &kb in &self.keyboards {
+            // The keyboard may be gone already, or typing into another text area
+            if unsafe { keyboard_types_into(kb, self.textarea) } {
+                unsafe { lvgl_sys::lv_keyboard_set_textarea(kb, ptr::null_mut()) };
+            }
+        }
+    }
+}
+
+unsafe fn keyboard_types_into(kb: *mut lv_obj_t, ta: *mut lv_obj_t) -> bool {
+    lvgl_sys::lv_obj_is_valid(kb)
+        && lvgl_sys::lv_obj_check_type(kb, &lvgl_sys::lv_keyboard_class)
+        && lvgl_sys::lv_keyboard_get_textarea(kb) == ta
+}
@@ -1,23 +1,71 @@
-/*
+use core::convert::TryFrom;
+use lvgl_sys::{lv_coord_t, lv_point_t};
+
+use crate::core::to_str;
 use crate::widgets::Label;
-use crate::{LvResult, NativeObject};
 
-impl Label {
-    pub fn set_label_align(&mut self, align: LabelAlign) -> LvResult<()> {
-        unsafe {
-            lvgl_sys::lv_label_set_align(self.core.raw()?.as_mut(), align as u8);
-        }
-        Ok(())
+crate::native_enum! {
+    lvgl_sys::lv_label_long_mode_t,
+    /// How a label shows a text too long for its size
+    pub enum LongMode {
+        /// Wrap the lines, growing the height of the label if it is sized to its content
+        Wrap = lvgl_sys::LV_LABEL_LONG_WRAP,
+        /// End the text with dots
+        Dot = lvgl_sys::LV_LABEL_LONG_DOT,
+        /// Scroll the text back and forth
+        Scroll = lvgl_sys::LV_LABEL_LONG_SCROLL,
+        /// Scroll the text in circles
+        ScrollCircular = lvgl_sys::LV_LABEL_LONG_SCROLL_CIRCULAR,
+        /// Cut the text
+        Clip = lvgl_sys::LV_LABEL_LONG_CLIP,
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[repr(u8)]
-pub enum LabelAlign {
-    Left = lvgl_sys::LV_LABEL_ALIGN_LEFT as u8,
-    Center = lvgl_sys::LV_LABEL_ALIGN_CENTER as u8,
-    Right = lvgl_sys::LV_LABEL_ALIGN_RIGHT as u8,
-    Auto = lvgl_sys::LV_LABEL_ALIGN_AUTO as u8,
-}
+impl<C: 'static> Label<C> {
+    pub fn text(&self) -> &str {
+        to_str(unsafe { lvgl_sys::lv_label_get_text(&*self.raw) })
+    }
 
-*/
+    pub fn long_mode(&self) -> LongMode {
+        LongMode::try_from(unsafe { lvgl_sys::lv_label_get_long_mode(&*self.raw) }).unwrap()
+    }
+
+    /// Whether commands like `#ff0000 red#` recolor the text, see `set_recolor()`
+    pub fn recolor(&self) -> bool {
+        unsafe { lvgl_sys::lv_label_get_recolor(&*self.raw) }
+    }
+
+    /// The selected characters, from the first to the last excluded, see `set_text_sel_start()`
+    /// and `set_text_sel_end()`
+    pub fn text_selection(&self) -> Option<(u32, u32)> {
+        let start = unsafe { lvgl_sys::lv_label_get_text_selection_start(&*self.raw) };
+        let end = unsafe { lvgl_sys::lv_label_get_text_selection_end(&*self.raw) };
+        let none = lvgl_sys::LV_DRAW_LABEL_NO_TXT_SEL as u32;
+        if start == none || end == none {
+            None
+        } else {
+            Some((start.min(end), start.max(end)))
+        }
+    }
+
+    /// Where the character `char_id` is drawn, relative to the label. The label must have been
+    /// laid out.
+    pub fn letter_pos(&self, char_id: u32) -> (lv_coord_t, lv_coord_t) {
+        let mut pos = lv_point_t { x: 0, y: 0 };
+        unsafe { lvgl_sys::lv_label_get_letter_pos(&*self.raw, char_id, &mut pos) };
+        (pos.x, pos.y)
+    }
+
+    /// The character closest to a position relative to the label, e.g. to place a cursor. The
+    /// label must have been laid out.
+    pub fn letter_on(&self, x: lv_coord_t, y: lv_coord_t) -> u32 {
+        let mut pos = lv_point_t { x, y };
+        unsafe { lvgl_sys::lv_label_get_letter_on(&*self.raw, &mut pos) }
+    }
+
+    /// Whether a character is drawn at a position relative to the label
+    pub fn is_char_under_pos(&self, x: lv_coord_t, y: lv_coord_t) -> bool {
+        let mut pos = lv_point_t { x, y };
+        unsafe { lvgl_sys::lv_label_is_char_under_pos(&*self.raw, &mut pos) }
+    }
+}
@@ -0,0 +1,275 @@
+use core::ptr::NonNull;
+use lvgl_sys::{lv_coord_t, lv_meter_indicator_t, lv_meter_scale_t, lv_obj_t};
+
+use crate::core::{to_lv_color, PixelColor};
+use crate::image::ImageSource;
+use crate::widgets::{Meter, ObjId};
+
+impl<C: 'static> Meter<C> {
+    /// Add a scale. Its ticks and indicators are drawn once it has a range and some ticks.
+    pub fn add_scale(&mut self) -> MeterScale {
+        let raw = unsafe { lvgl_sys::lv_meter_add_scale(&mut *self.raw) };
+        MeterScale {
+            meter: ObjId::of(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    /// Draw `count` ticks over the angle of a scale
+    pub fn set_scale_ticks(
+        &mut self,
+        scale: &mut MeterScale,
+        count: u16,
+        width: u16,
+        len: u16,
+        color: PixelColor,
+    ) -> &mut Self {
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_ticks(
+                &mut *self.raw,
+                scale,
+                count,
+                width,
+                len,
+                to_lv_color(color),
+            )
+        };
+        self
+    }
+
+    /// Make every `nth` tick of a scale a major tick, labelled with its value `label_gap` pixels
+    /// away
+    pub fn set_scale_major_ticks(
+        &mut self,
+        scale: &mut MeterScale,
+        nth: u16,
+        width: u16,
+        len: u16,
+        color: PixelColor,
+        label_gap: i16,
+    ) -> &mut Self {
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_major_ticks(
+                &mut *self.raw,
+                scale,
+                nth,
+                width,
+                len,
+                to_lv_color(color),
+                label_gap,
+            )
+        };
+        self
+    }
+
+    /// Map the values of a scale from `min` to `max` on `angle_range` degrees, starting
+    /// `rotation` degrees clockwise from the right
+    pub fn set_scale_range(
+        &mut self,
+        scale: &mut MeterScale,
+        min: i32,
+        max: i32,
+        angle_range: u16,
+        rotation: u16,
+    ) -> &mut Self {
+        assert!(min <= max, "The range of the scale is empty");
+        let scale = self.scale_ptr(scale);
+        unsafe {
+            lvgl_sys::lv_meter_set_scale_range(
+                &mut *self.raw,
+                scale,
+                min,
+                max,
+                angle_range as u32,
+                rotation as u32,
+            )
+        };
+        self
+    }
+
+    /// The minimum and maximum values of a scale
+    pub fn scale_range(&self, scale: &MeterScale) -> (i32, i32) {
+        let scale = unsafe { &*self.scale_ptr(scale) };
+        (scale.min, scale.max)
+    }
+
+    /// Add a needle drawn as a line, `r_mod` pixels longer than the radius of the scale
+    pub fn add_needle_line(
+        &mut self,
+        scale: &mut MeterScale,
+        width: u16,
+        color: PixelColor,
+        r_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_needle_line(
+                &mut *self.raw,
+                scale,
+                width,
+                to_lv_color(color),
+                r_mod,
+            )
+        };
+        self.indicator(raw)
+    }
+
+    /// Add a needle drawn as an image pointing to the right, rotated around `(pivot_x, pivot_y)`.
+    /// LVGL keeps a pointer to the source, hence the `'static`.
+    pub fn add_needle_img(
+        &mut self,
+        scale: &mut MeterScale,
+        src: ImageSource<'static>,
+        pivot_x: lv_coord_t,
+        pivot_y: lv_coord_t,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_needle_img(&mut *self.raw, scale, src.as_ptr(), pivot_x, pivot_y)
+        };
+        self.indicator(raw)
+    }
+
+    /// Add an arc between the start and the end values, with an outer radius `r_mod` pixels
+    /// larger than the radius of the scale
+    pub fn add_arc(
+        &mut self,
+        scale: &mut MeterScale,
+        width: u16,
+        color: PixelColor,
+        r_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_arc(&mut *self.raw, scale, width, to_lv_color(color), r_mod)
+        };
+        self.indicator(raw)
+    }
+
+    /// Recolor the ticks between the start and the end values with a gradient, and widen them by
+    /// `width_mod` pixels. The gradient spans the indicator if `local`, the whole scale otherwise.
+    pub fn add_scale_lines(
+        &mut self,
+        scale: &mut MeterScale,
+        color_start: PixelColor,
+        color_end: PixelColor,
+        local: bool,
+        width_mod: i16,
+    ) -> MeterIndicator {
+        let scale = self.scale_ptr(scale);
+        let raw = unsafe {
+            lvgl_sys::lv_meter_add_scale_lines(
+                &mut *self.raw,
+                scale,
+                to_lv_color(color_start),
+                to_lv_color(color_end),
+                local,
+                width_mod,
+            )
+        };
+        self.indicator(raw)
+    }
+
+    /// Set both the start and the end values of an indicator, e.g. for needles
+    pub fn set_indicator_value(&mut self, indicator: &mut MeterIndicator, value: i32) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        let value = clamp(indicator, value);
+        unsafe { lvgl_sys::lv_meter_set_indicator_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    /// Where arcs and scale lines start
+    pub fn set_indicator_start_value(
+        &mut self,
+        indicator: &mut MeterIndicator,
+        value: i32,
+    ) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        let value = clamp(indicator, value);
+        unsafe { lvgl_sys::lv_meter_set_indicator_start_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    /// Where arcs and scale lines end
+    pub fn set_indicator_end_value(
+        &mut self,
+        indicator: &mut MeterIndicator,
+        value: i32,
+    ) -> &mut Self {
+        let indicator = self.indicator_ptr(indicator);
+        let value = clamp(indicator, value);
+        unsafe { lvgl_sys::lv_meter_set_indicator_end_value(&mut *self.raw, indicator, value) };
+        self
+    }
+
+    /// The start and end values of an indicator
+    pub fn indicator_values(&self, indicator: &MeterIndicator) -> (i32, i32) {
+        let indicator = unsafe { &*self.indicator_ptr(indicator) };
+        (indicator.start_value, indicator.end_value)
+    }
+
+    // LVGL can't remove scales and indicators, they live as long as the meter that added them
+    fn scale_ptr(&self, scale: &MeterScale) -> *mut lv_meter_scale_t {
+        assert!(
+            scale.meter == ObjId::of(self.raw_ptr()),
+            "The scale is not one of this meter"
+        );
+        scale.raw.as_ptr()
+    }
+
+    fn indicator_ptr(&self, indicator: &MeterIndicator) -> *mut lv_meter_indicator_t {
+        assert!(
+            indicator.meter == ObjId::of(self.raw_ptr()),
+            "The indicator is not one of this meter"
+        );
+        indicator.raw.as_ptr()
+    }
+
+    fn indicator(&mut self, raw: *mut lv_meter_indicator_t) -> MeterIndicator {
+        MeterIndicator {
+            meter: ObjId::of(&mut *self.raw),
+            raw: NonNull::new(raw).expect("OOM"),
+        }
+    }
+
+    // The id of the meter is attached to it on first use, even to read a scale
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+// LVGL would draw needles past the ends of the scale
+fn clamp(indicator: *mut lv_meter_indicator_t, value: i32) -> i32 {
+    let scale = unsafe { &*(*indicator).scale };
+    value.max(scale.min).min(scale.max)
+}
+
+/// A scale of a meter, see `Meter::add_scale()`. It is only used through its meter, e.g.
+/// `Meter::set_scale_range()`.
+pub struct MeterScale {
+    meter: ObjId,
+    raw: NonNull<lv_meter_scale_t>,
+}
+
+impl MeterScale {
+    pub fn raw(&mut self) -> *mut lv_meter_scale_t {
+        self.raw.as_ptr()
+    }
+}
+
+/// An indicator of a meter, see `Meter::add_needle_line()` and the like. It is only used
+/// through its meter, e.g. `Meter::set_indicator_value()`.
+///
+/// Values are clamped to the range of the scale, as LVGL would draw needles past its ends.
+pub struct MeterIndicator {
+    meter: ObjId,
+    raw: NonNull<lv_meter_indicator_t>,
+}
+
+impl MeterIndicator {
+    pub fn raw(&mut self) -> *mut lv_meter_indicator_t {
+        self.raw.as_ptr()
+    }
+}
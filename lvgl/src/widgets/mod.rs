@@ -1,13 +1,93 @@
+#[cfg(lv_use_arc)]
 mod arc;
+#[cfg(lv_use_bar)]
 mod bar;
-mod gauge;
+#[cfg(lv_use_btnmatrix)]
+mod btnmatrix;
+#[cfg(lv_use_calendar)]
+mod calendar;
+#[cfg(lv_use_canvas)]
+mod canvas;
+#[cfg(lv_use_chart)]
+mod chart;
+#[cfg(lv_use_dropdown)]
+mod dropdown;
+#[cfg(lv_use_img)]
+mod img;
+#[cfg(lv_use_imgbtn)]
+mod imgbtn;
+#[cfg(lv_use_keyboard)]
+mod keyboard;
+#[cfg(lv_use_label)]
 mod label;
+#[cfg(lv_use_meter)]
+mod meter;
+#[cfg(lv_use_msgbox)]
+mod msgbox;
+#[cfg(lv_use_roller)]
+mod roller;
+#[cfg(lv_use_table)]
+mod table;
+#[cfg(lv_use_tabview)]
+mod tabview;
+#[cfg(lv_use_textarea)]
+mod textarea;
+#[cfg(lv_use_tileview)]
+mod tileview;
 
 use crate::core::Obj;
 
 include!(concat!(env!("OUT_DIR"), "/generated-widgets.rs"));
 
+#[cfg(lv_use_arc)]
 pub use arc::*;
+#[cfg(lv_use_bar)]
 pub use bar::*;
-pub use gauge::*;
+#[cfg(lv_use_btnmatrix)]
+pub use btnmatrix::*;
+#[cfg(lv_use_calendar)]
+pub use calendar::*;
+#[cfg(lv_use_chart)]
+pub use chart::*;
+#[cfg(lv_use_keyboard)]
+pub use keyboard::*;
+#[cfg(lv_use_label)]
 pub use label::*;
+#[cfg(lv_use_meter)]
+pub use meter::*;
+#[cfg(lv_use_msgbox)]
+pub use msgbox::*;
+#[cfg(lv_use_roller)]
+pub use roller::*;
+#[cfg(lv_use_table)]
+pub use table::*;
+#[cfg(lv_use_tabview)]
+pub use tabview::*;
+#[cfg(lv_use_textarea)]
+pub use textarea::*;
+#[cfg(lv_use_tileview)]
+pub use tileview::*;
+
+/// Identifies an object for the whole run, unlike its address which LVGL reuses once it is
+/// deleted. Handles to the parts of a widget, e.g. the series of a chart, keep the id of their
+/// widget so that they are only used through it.
+#[cfg(any(lv_use_chart, lv_use_meter))]
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct ObjId(usize);
+
+#[cfg(any(lv_use_chart, lv_use_meter))]
+impl ObjId {
+    pub(crate) fn of(obj_raw: *mut lvgl_sys::lv_obj_t) -> Self {
+        *crate::core::attached::<ObjId>(unsafe { &mut *obj_raw })
+    }
+}
+
+// Only created by `attached()`, once per object
+#[cfg(any(lv_use_chart, lv_use_meter))]
+impl Default for ObjId {
+    fn default() -> Self {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        ObjId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
@@ -0,0 +1,157 @@
+use core::ptr;
+use lvgl_sys::lv_obj_t;
+
+use crate::core::{add_event_cb, attached, to_str, with_cstr, Event, Obj, ObjExt, Screen};
+use crate::widgets::{BtnMap, Btnmatrix};
+
+define_object!(
+    /// A message box with a title, a text, a row of buttons and an optional close button
+    Msgbox
+);
+
+impl<C: 'static> Msgbox<C> {
+    /// Create a message box in `parent`. An empty `title` or `text` is left out, as are the
+    /// buttons if there are none.
+    pub fn new(
+        parent: &mut impl ObjExt<C>,
+        title: impl AsRef<str>,
+        text: impl AsRef<str>,
+        buttons: &[&str],
+        close_btn: bool,
+    ) -> Self {
+        let obj = create(
+            &mut *parent.raw,
+            title.as_ref(),
+            text.as_ref(),
+            buttons,
+            close_btn,
+        );
+        Self {
+            obj: Obj::from_raw(obj, parent.context),
+        }
+    }
+
+    /// Create a modal message box, on the top layer behind a backdrop blocking the clicks to the
+    /// screens. Its callbacks receive the context of `screen`.
+    pub fn modal(
+        screen: &mut Screen<C>,
+        title: impl AsRef<str>,
+        text: impl AsRef<str>,
+        buttons: &[&str],
+        close_btn: bool,
+    ) -> Self {
+        let obj = create(
+            ptr::null_mut(),
+            title.as_ref(),
+            text.as_ref(),
+            buttons,
+            close_btn,
+        );
+        Self {
+            obj: Obj::from_raw(obj, screen.context),
+        }
+    }
+
+    /// The matrix of the buttons, e.g. to add it to a group
+    pub fn btns(&mut self) -> Option<Btnmatrix<C>> {
+        let btns = unsafe { lvgl_sys::lv_msgbox_get_btns(&mut *self.raw).as_mut()? };
+        Some(Btnmatrix {
+            obj: Obj::from_raw(btns, self.context),
+        })
+    }
+
+    /// The index of the last pressed button
+    pub fn active_btn(&self) -> Option<u16> {
+        active_btn(self.raw_ptr()).map(|(btn_id, _)| btn_id)
+    }
+
+    /// The text of the last pressed button
+    pub fn active_btn_text(&self) -> Option<&str> {
+        active_btn(self.raw_ptr()).map(|(_, text)| text)
+    }
+
+    /// Register a callback for when a button is clicked, receiving its index and its text. The
+    /// message box is closed if it returns `true`.
+    pub fn on_btn_clicked(
+        &mut self,
+        mut f: impl FnMut(&mut C, u16, &str) -> bool + 'static,
+    ) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, target| {
+                // The buttons bubble up their events
+                let btns = unsafe { lvgl_sys::lv_msgbox_get_btns(current_target) };
+                if btns != target as *mut lv_obj_t {
+                    return;
+                }
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                if let Some((btn_id, text)) = active_btn(current_target) {
+                    if f(context, btn_id, text) {
+                        // Deleting the message box now would pull it from under LVGL
+                        unsafe { lvgl_sys::lv_msgbox_close_async(current_target) };
+                    }
+                }
+            },
+        );
+        self
+    }
+
+    /// Delete the message box, and its backdrop if it is modal
+    pub fn close(self) {
+        unsafe { lvgl_sys::lv_msgbox_close(self.obj.raw) };
+    }
+
+    /// Like `close()`, but deleted on the next refresh, e.g. from one of its own callbacks
+    pub fn close_async(self) {
+        unsafe { lvgl_sys::lv_msgbox_close_async(self.obj.raw) };
+    }
+
+    // LVGL takes a mutable message box even to read its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+fn create(
+    parent: *mut lv_obj_t,
+    title: &str,
+    text: &str,
+    buttons: &[&str],
+    close_btn: bool,
+) -> &'static mut lv_obj_t {
+    let mut map = (!buttons.is_empty()).then(|| BtnMap::new(&[buttons]));
+    let map_ptr = map.as_mut().map_or(ptr::null_mut(), |map| map.as_ptr());
+    let obj = with_cstr(title, |title| {
+        with_cstr(text, |text| unsafe {
+            lvgl_sys::lv_msgbox_create(parent, title, text, map_ptr, close_btn)
+        })
+    });
+    let obj = unsafe { obj.as_mut().expect("OOM") };
+
+    // The button matrix only points to its map
+    if let Some(map) = map {
+        let btns = unsafe { lvgl_sys::lv_msgbox_get_btns(&mut *obj) };
+        *attached::<BtnMap>(unsafe { &mut *btns }) = map;
+    }
+    obj
+}
+
+fn active_btn<'a>(msgbox: *mut lv_obj_t) -> Option<(u16, &'a str)> {
+    unsafe {
+        let btns = lvgl_sys::lv_msgbox_get_btns(msgbox);
+        if btns.is_null() {
+            return None;
+        }
+        let btn_id = lvgl_sys::lv_btnmatrix_get_selected_btn(btns);
+        if btn_id == lvgl_sys::LV_BTNMATRIX_BTN_NONE as u16 {
+            return None;
+        }
+        Some((
+            btn_id,
+            to_str(lvgl_sys::lv_btnmatrix_get_btn_text(btns, btn_id)),
+        ))
+    }
+}
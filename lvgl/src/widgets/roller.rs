@@ -0,0 +1,75 @@
+use alloc::string::{String, ToString};
+use core::convert::TryFrom;
+
+use crate::core::{add_event_cb, join_lines, to_str, with_cstr, Event};
+use crate::style::Animation;
+use crate::widgets::Roller;
+
+crate::native_enum! {
+    lvgl_sys::lv_roller_mode_t,
+    pub enum RollerMode {
+        /// Stop at the first and the last options
+        Normal = lvgl_sys::LV_ROLLER_MODE_NORMAL,
+        /// Go back to the first option after the last one
+        Infinite = lvgl_sys::LV_ROLLER_MODE_INFINITE,
+    }
+}
+
+impl<C: 'static> Roller<C> {
+    /// Set the options, one per row. LVGL copies them.
+    pub fn set_options<T: AsRef<str>>(
+        &mut self,
+        options: impl IntoIterator<Item = T>,
+        mode: RollerMode,
+    ) -> &mut Self {
+        let options = join_lines(options);
+        with_cstr(&options, |options| unsafe {
+            lvgl_sys::lv_roller_set_options(&mut *self.raw, options, mode.into())
+        });
+        self
+    }
+
+    pub fn options(&self) -> impl Iterator<Item = &str> {
+        // Infinite rollers repeat their options
+        let options = to_str(unsafe { lvgl_sys::lv_roller_get_options(&*self.raw) });
+        options.split('\n').take(self.option_count())
+    }
+
+    pub fn option_count(&self) -> usize {
+        unsafe { lvgl_sys::lv_roller_get_option_cnt(&*self.raw) as usize }
+    }
+
+    /// Select an option, the last one if `index` is past the end, scrolling to it with `anim`
+    pub fn set_selected(&mut self, index: usize, anim: Animation) -> &mut Self {
+        let index = u16::try_from(index).unwrap_or(u16::MAX);
+        unsafe { lvgl_sys::lv_roller_set_selected(&mut *self.raw, index, anim.into()) };
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        unsafe { lvgl_sys::lv_roller_get_selected(&*self.raw) as usize }
+    }
+
+    pub fn selected_str(&self) -> String {
+        self.options()
+            .nth(self.selected())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Register a callback for when an option is selected, receiving its index
+    pub fn on_selected(&mut self, mut f: impl FnMut(&mut C, usize) + 'static) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, _child| {
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                let selected = unsafe { lvgl_sys::lv_roller_get_selected(current_target) };
+                f(context, selected as usize)
+            },
+        );
+        self
+    }
+}
@@ -0,0 +1,110 @@
+use lvgl_sys::{lv_coord_t, lv_obj_t};
+
+use crate::core::{add_event_cb, to_str, Event};
+use crate::widgets::Table;
+
+bitflags! {
+    /// How a cell of a table is drawn
+    pub struct CellCtrl: lvgl_sys::lv_table_cell_ctrl_t {
+        /// Merge the cell with the one on its right
+        const MERGE_RIGHT = lvgl_sys::LV_TABLE_CELL_CTRL_MERGE_RIGHT;
+        /// Cut the text instead of growing the row
+        const TEXT_CROP = lvgl_sys::LV_TABLE_CELL_CTRL_TEXT_CROP;
+        const CUSTOM_1 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_1;
+        const CUSTOM_2 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_2;
+        const CUSTOM_3 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_3;
+        const CUSTOM_4 = lvgl_sys::LV_TABLE_CELL_CTRL_CUSTOM_4;
+    }
+}
+
+impl From<CellCtrl> for lvgl_sys::lv_table_cell_ctrl_t {
+    fn from(ctrl: CellCtrl) -> Self {
+        ctrl.bits()
+    }
+}
+
+impl<C: 'static> Table<C> {
+    /// Fill the table row by row, with as many columns as the longest row. The cells missing
+    /// from shorter rows are emptied.
+    pub fn set_data(&mut self, rows: &[&[&str]]) -> &mut Self {
+        let col_cnt = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        self.set_row_cnt(rows.len() as u16);
+        self.set_col_cnt(col_cnt as u16);
+        for (row_id, row) in rows.iter().enumerate() {
+            for col_id in 0..col_cnt {
+                let value = row.get(col_id).copied().unwrap_or_default();
+                self.set_cell_value(row_id as u16, col_id as u16, value);
+            }
+        }
+        self
+    }
+
+    /// Set the width of each column, as many as there are widths
+    pub fn set_col_widths(&mut self, widths: &[lv_coord_t]) -> &mut Self {
+        self.set_col_cnt(widths.len() as u16);
+        for (col_id, width) in widths.iter().enumerate() {
+            self.set_col_width(col_id as u16, *width);
+        }
+        self
+    }
+
+    pub fn cell_value(&self, row: u16, col: u16) -> &str {
+        to_str(unsafe { lvgl_sys::lv_table_get_cell_value(self.raw_ptr(), row, col) })
+    }
+
+    pub fn row_count(&self) -> u16 {
+        unsafe { lvgl_sys::lv_table_get_row_cnt(self.raw_ptr()) }
+    }
+
+    pub fn col_count(&self) -> u16 {
+        unsafe { lvgl_sys::lv_table_get_col_cnt(self.raw_ptr()) }
+    }
+
+    pub fn col_width(&self, col: u16) -> lv_coord_t {
+        unsafe { lvgl_sys::lv_table_get_col_width(self.raw_ptr(), col) }
+    }
+
+    pub fn has_cell_ctrl(&self, row: u16, col: u16, ctrl: CellCtrl) -> bool {
+        unsafe { lvgl_sys::lv_table_has_cell_ctrl(self.raw_ptr(), row, col, ctrl.into()) }
+    }
+
+    /// The `(row, col)` of the pressed cell, or of the cell selected with the keys
+    pub fn selected_cell(&self) -> Option<(u16, u16)> {
+        selected_cell(self.raw_ptr())
+    }
+
+    /// Register a callback for when a cell is clicked, or selected with the keys, receiving its
+    /// row and its column
+    pub fn on_cell_clicked(&mut self, mut f: impl FnMut(&mut C, u16, u16) + 'static) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, _child| {
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                if let Some((row, col)) = selected_cell(current_target) {
+                    f(context, row, col)
+                }
+            },
+        );
+        self
+    }
+
+    // LVGL takes a mutable table even to read its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+fn selected_cell(table: *mut lv_obj_t) -> Option<(u16, u16)> {
+    let mut row = 0;
+    let mut col = 0;
+    unsafe { lvgl_sys::lv_table_get_selected_cell(table, &mut row, &mut col) };
+    let none = lvgl_sys::LV_TABLE_CELL_NONE as u16;
+    if row == none || col == none {
+        None
+    } else {
+        Some((row, col))
+    }
+}
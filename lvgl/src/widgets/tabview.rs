@@ -0,0 +1,91 @@
+use core::convert::TryFrom;
+use lvgl_sys::{lv_coord_t, lv_obj_t};
+
+use crate::core::{add_event_cb, with_cstr, Event, Obj, ObjExt};
+use crate::style::{Animation, Dir};
+
+define_object!(
+    /// Pages shown one at a time, picked with a row of buttons or by swiping
+    Tabview
+);
+
+define_object!(
+    /// The page of a tab, in which widgets can be created
+    Tab
+);
+
+impl<C: 'static> Tabview<C> {
+    /// Create a tabview with its buttons on the `tab_pos` side, `tab_size` high or wide
+    pub fn new(parent: &mut impl ObjExt<C>, tab_pos: Dir, tab_size: lv_coord_t) -> Self {
+        unsafe {
+            let obj = lvgl_sys::lv_tabview_create(&mut *parent.raw, tab_pos.into(), tab_size);
+            let obj = Obj::from_raw(obj.as_mut().expect("OOM"), parent.context);
+            Self { obj }
+        }
+    }
+
+    /// Add a tab after the others. LVGL copies its name.
+    pub fn add_tab(&mut self, name: impl AsRef<str>) -> Tab<C> {
+        let tab = with_cstr(name.as_ref(), |name| unsafe {
+            lvgl_sys::lv_tabview_add_tab(&mut *self.raw, name)
+        });
+        let tab = unsafe { tab.as_mut().expect("OOM") };
+        Tab {
+            obj: Obj::from_raw(tab, self.context),
+        }
+    }
+
+    pub fn rename_tab(&mut self, index: usize, name: impl AsRef<str>) -> &mut Self {
+        with_cstr(name.as_ref(), |name| unsafe {
+            lvgl_sys::lv_tabview_rename_tab(&mut *self.raw, index as u32, name)
+        });
+        self
+    }
+
+    pub fn tab_count(&self) -> usize {
+        unsafe {
+            let content = lvgl_sys::lv_tabview_get_content(self.raw_ptr());
+            lvgl_sys::lv_obj_get_child_cnt(content) as usize
+        }
+    }
+
+    /// Show a tab, the last one if `index` is past the end
+    pub fn set_act(&mut self, index: usize, anim: Animation) -> &mut Self {
+        let index = u32::try_from(index).unwrap_or(u32::MAX);
+        unsafe { lvgl_sys::lv_tabview_set_act(&mut *self.raw, index, anim.into()) };
+        self
+    }
+
+    /// The index of the shown tab
+    pub fn active_tab(&self) -> usize {
+        unsafe { lvgl_sys::lv_tabview_get_tab_act(self.raw_ptr()) as usize }
+    }
+
+    /// Register a callback for when a tab is picked with its button or swiped to, receiving its
+    /// index. Tabs shown with `set_act()` are not reported.
+    pub fn on_tab_changed(&mut self, mut f: impl FnMut(&mut C, usize) + 'static) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, target| {
+                // The buttons bubble up their events, unlike the widgets of the tabs
+                let target = target as *mut lv_obj_t;
+                let btns = unsafe { lvgl_sys::lv_tabview_get_tab_btns(current_target) };
+                if target != current_target as *mut lv_obj_t && target != btns {
+                    return;
+                }
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                let active = unsafe { lvgl_sys::lv_tabview_get_tab_act(current_target) };
+                f(context, active as usize)
+            },
+        );
+        self
+    }
+
+    // LVGL takes a mutable tabview even to read its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::string::String;
+use core::sync::atomic::{AtomicBool, Ordering};
+use cstr_core::{CStr, CString};
+use lvgl_sys::lv_obj_t;
+
+use crate::core::{add_event_cb_with_param, attached, to_str, with_cstr, Event};
+use crate::widgets::Textarea;
+
+/// What to do with the text being inserted, see `Textarea::on_insert()`
+pub enum InsertAction {
+    /// Insert the text
+    Keep,
+    /// Don't insert anything
+    Veto,
+    /// Insert this text instead
+    Replace(String),
+}
+
+const DELETE: &str = "\u{7f}";
+
+/// Set while `Textarea::on_insert()` inserts a replacement, whose own insert events are not
+/// reported. LVGL sends them before `lv_textarea_add_text()` returns.
+static REPLACING: AtomicBool = AtomicBool::new(false);
+
+/// The accepted characters, to which LVGL only points
+#[derive(Default)]
+struct AcceptedChars(Option<CString>);
+
+impl<C: 'static> Textarea<C> {
+    pub fn text(&self) -> &str {
+        to_str(unsafe { lvgl_sys::lv_textarea_get_text(&*self.raw) })
+    }
+
+    pub fn placeholder_text(&self) -> &str {
+        to_str(unsafe { lvgl_sys::lv_textarea_get_placeholder_text(self.raw_ptr()) })
+    }
+
+    /// The position of the cursor, in characters
+    pub fn cursor_pos(&self) -> u32 {
+        unsafe { lvgl_sys::lv_textarea_get_cursor_pos(&*self.raw) }
+    }
+
+    /// Put the cursor after the last character
+    pub fn set_cursor_last(&mut self) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_textarea_set_cursor_pos(
+                &mut *self.raw,
+                lvgl_sys::LV_TEXTAREA_CURSOR_LAST as i32,
+            )
+        };
+        self
+    }
+
+    /// Select the characters from `start` to `end`, excluded. Selecting text with the pointer
+    /// is enabled with `set_text_selection()`.
+    pub fn select(&mut self, start: u32, end: u32) -> &mut Self {
+        let label = self.label();
+        unsafe {
+            lvgl_sys::lv_label_set_text_sel_start(label, start);
+            lvgl_sys::lv_label_set_text_sel_end(label, end);
+        }
+        self
+    }
+
+    /// The selected characters, from the first to the last excluded
+    pub fn selection(&self) -> Option<(u32, u32)> {
+        if !unsafe { lvgl_sys::lv_textarea_text_is_selected(&*self.raw) } {
+            return None;
+        }
+        let label = self.label();
+        let start = unsafe { lvgl_sys::lv_label_get_text_selection_start(label) };
+        let end = unsafe { lvgl_sys::lv_label_get_text_selection_end(label) };
+        // The selection goes backwards when made from right to left
+        Some((start.min(end), start.max(end)))
+    }
+
+    /// Only accept these characters, typed or inserted
+    pub fn set_accepted_chars(&mut self, chars: &str) -> &mut Self {
+        let chars = to_cstring(chars);
+        unsafe { lvgl_sys::lv_textarea_set_accepted_chars(&mut *self.raw, chars.as_ptr()) };
+        // The previous list is dropped only once LVGL doesn't point to it anymore
+        attached::<AcceptedChars>(&mut *self.raw).0 = Some(chars);
+        self
+    }
+
+    /// Accept all the characters
+    pub fn clear_accepted_chars(&mut self) -> &mut Self {
+        unsafe { lvgl_sys::lv_textarea_set_accepted_chars(&mut *self.raw, core::ptr::null()) };
+        attached::<AcceptedChars>(&mut *self.raw).0 = None;
+        self
+    }
+
+    /// The maximum number of characters, 0 for no limit
+    pub fn max_length(&self) -> u32 {
+        unsafe { lvgl_sys::lv_textarea_get_max_length(self.raw_ptr()) }
+    }
+
+    pub fn password_mode(&self) -> bool {
+        unsafe { lvgl_sys::lv_textarea_get_password_mode(&*self.raw) }
+    }
+
+    /// Register a callback for the `Insert` event, receiving the text about to be inserted,
+    /// typed or added. The text it is replaced with is inserted without calling it again, and
+    /// deletions are not reported.
+    pub fn on_insert(
+        &mut self,
+        mut f: impl FnMut(&mut C, &str) -> InsertAction + 'static,
+    ) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb_with_param(
+            self.raw,
+            Some(Event::Insert),
+            move |_e, current_target, _child, param| {
+                if REPLACING.load(Ordering::Relaxed) {
+                    return;
+                }
+                let text = unsafe { CStr::from_ptr(param as *const cty::c_char) };
+                let text = match text.to_str() {
+                    // Deleting a character is reported as inserting `LV_KEY_DEL`
+                    Ok(DELETE) | Err(_) => return,
+                    Ok(text) => text,
+                };
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                match f(context, text) {
+                    InsertAction::Keep => return,
+                    InsertAction::Replace(new_text) if new_text == text => return,
+                    InsertAction::Replace(new_text) => {
+                        REPLACING.store(true, Ordering::Relaxed);
+                        with_cstr(&new_text, |new_text| unsafe {
+                            lvgl_sys::lv_textarea_add_text(current_target, new_text)
+                        });
+                        REPLACING.store(false, Ordering::Relaxed);
+                    }
+                    InsertAction::Veto => {}
+                }
+                // LVGL drops the text when the replacement is empty. Set last, as inserting text
+                // resets it.
+                let empty = b"\0".as_ptr() as *const cty::c_char;
+                unsafe { lvgl_sys::lv_textarea_set_insert_replace(current_target, empty) };
+            },
+        );
+        self
+    }
+
+    fn label(&self) -> *mut lv_obj_t {
+        unsafe { lvgl_sys::lv_textarea_get_label(&*self.raw) }
+    }
+
+    // LVGL takes a mutable text area even to read some of its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+fn to_cstring(text: &str) -> CString {
+    CString::new(text).expect("Text with a nul byte")
+}
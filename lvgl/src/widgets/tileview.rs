@@ -0,0 +1,81 @@
+use lvgl_sys::lv_obj_t;
+
+use crate::core::{add_event_cb, Event, Obj};
+use crate::style::{Animation, Dir};
+use crate::widgets::Tileview;
+
+define_object!(
+    /// A tile of a tileview, in which widgets can be created
+    Tile
+);
+
+impl<C: 'static> Tileview<C> {
+    /// Add a tile at a column and a row of the grid, from which the user can swipe in `dir` to
+    /// the neighbouring tiles
+    pub fn add_tile(&mut self, col: u8, row: u8, dir: Dir) -> Tile<C> {
+        let tile = unsafe { lvgl_sys::lv_tileview_add_tile(&mut *self.raw, col, row, dir.into()) };
+        let tile = unsafe { tile.as_mut().expect("OOM") };
+        Tile {
+            obj: Obj::from_raw(tile, self.context),
+        }
+    }
+
+    /// Show the tile at a column and a row, if there is one
+    pub fn set_act(&mut self, col: u8, row: u8, anim: Animation) -> &mut Self {
+        unsafe {
+            lvgl_sys::lv_obj_set_tile_id(&mut *self.raw, col.into(), row.into(), anim.into())
+        };
+        self
+    }
+
+    pub fn set_act_tile(&mut self, tile: &mut Tile<C>, anim: Animation) -> &mut Self {
+        unsafe { lvgl_sys::lv_obj_set_tile(&mut *self.raw, &mut *tile.raw, anim.into()) };
+        self
+    }
+
+    /// The column and the row of the shown tile, if one was shown with `set_act()` or swiped to
+    pub fn active_tile(&self) -> Option<(u8, u8)> {
+        active_tile(self.raw_ptr())
+    }
+
+    /// Register a callback for when a tile is swiped to, receiving its column and its row
+    pub fn on_tile_changed(&mut self, mut f: impl FnMut(&mut C, u8, u8) + 'static) -> &mut Self {
+        let mut context = self.context;
+        add_event_cb(
+            self.raw,
+            Some(Event::ValueChanged),
+            move |_e, current_target, target| {
+                if target as *mut lv_obj_t != current_target as *mut lv_obj_t {
+                    return;
+                }
+                let context = unsafe { context.as_mut().as_mut() };
+                let context = context.expect("screen.context() must be set");
+                if let Some((col, row)) = active_tile(current_target) {
+                    f(context, col, row)
+                }
+            },
+        );
+        self
+    }
+
+    // LVGL takes a mutable tileview even to read its properties
+    fn raw_ptr(&self) -> *mut lv_obj_t {
+        &*self.raw as *const lv_obj_t as *mut lv_obj_t
+    }
+}
+
+fn active_tile(tileview: *mut lv_obj_t) -> Option<(u8, u8)> {
+    unsafe {
+        let tile = lvgl_sys::lv_tileview_get_tile_act(tileview);
+        if tile.is_null() {
+            return None;
+        }
+        // Tiles are as large as the tileview, placed at their column and row
+        lvgl_sys::lv_obj_update_layout(tileview);
+        let width = lvgl_sys::lv_obj_get_content_width(tileview).max(1);
+        let height = lvgl_sys::lv_obj_get_content_height(tileview).max(1);
+        let col = lvgl_sys::lv_obj_get_x(tile) / width;
+        let row = lvgl_sys::lv_obj_get_y(tile) / height;
+        Some((col as u8, row as u8))
+    }
+}
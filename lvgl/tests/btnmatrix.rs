@@ -0,0 +1,61 @@
+use lvgl::core::{ObjExt, Screen};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Btnmatrix, BtnmatrixCtrl};
+
+#[derive(Default)]
+struct Pressed {
+    btns: Vec<(u16, String)>,
+}
+
+#[test]
+fn btnmatrix_map_ctrls_and_callback() {
+    with_screen(200, 100, |lvgl, display, screen: &mut Screen<Pressed>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut btnm = Btnmatrix::new(screen);
+        btnm.set_size(200, 100);
+        btnm.set_pos(0, 0);
+        btnm.set_map(&[&["1", "2"], &["3", "OK"]]);
+        btnm.on_value_changed(|c, id, text| c.btns.push((id, text.to_string())));
+        assert_eq!(btnm.btn_count(), 4);
+        assert_eq!(btnm.btn_text(3), Some("OK"));
+        assert_eq!(btnm.selected_btn(), None);
+
+        assert!(settle(lvgl, display));
+
+        // Rows are 50 pixels high, buttons 100 pixels wide
+        input.click(lvgl, 150, 75);
+        input.click(lvgl, 50, 25);
+        assert_eq!(
+            screen.context().as_ref().unwrap().btns,
+            [(3, "OK".to_string()), (0, "1".to_string())]
+        );
+        assert_eq!(btnm.selected_btn(), Some(0));
+
+        // A disabled button doesn't send events
+        btnm.set_ctrl_map(&[
+            BtnmatrixCtrl::DISABLED,
+            BtnmatrixCtrl::empty(),
+            BtnmatrixCtrl::empty(),
+            BtnmatrixCtrl::empty(),
+        ]);
+        assert!(btnm.has_btn_ctrl(0, BtnmatrixCtrl::DISABLED));
+        input.click(lvgl, 50, 25);
+        assert_eq!(screen.context().as_ref().unwrap().btns.len(), 2);
+
+        // Replacing the map frees the previous one
+        btnm.set_map(&[&["A", "B", "C"]]);
+        assert_eq!(btnm.btn_count(), 3);
+        assert_eq!(btnm.btn_text(2), Some("C"));
+
+        // One checked
+        btnm.set_btn_ctrl_all(BtnmatrixCtrl::CHECKABLE);
+        btnm.set_one_checked(true);
+        assert!(btnm.one_checked());
+        assert!(settle(lvgl, display));
+        input.click(lvgl, 30, 50);
+        input.click(lvgl, 170, 50);
+        assert!(!btnm.has_btn_ctrl(0, BtnmatrixCtrl::CHECKED));
+        assert!(btnm.has_btn_ctrl(2, BtnmatrixCtrl::CHECKED));
+    });
+}
@@ -0,0 +1,62 @@
+use lvgl::core::{ObjExt, Screen};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Calendar, CalendarDate, CalendarHeaderArrow};
+
+#[derive(Default)]
+struct Clicks {
+    dates: Vec<CalendarDate>,
+}
+
+#[test]
+fn calendar_reports_clicked_dates() {
+    with_screen(210, 210, |lvgl, display, screen: &mut Screen<Clicks>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut calendar = Calendar::new(screen);
+        calendar.set_pos(0, 0);
+        calendar.set_size(210, 210);
+
+        calendar.set_today(CalendarDate::new(2024, 2, 14));
+        assert_eq!(calendar.today(), CalendarDate::new(2024, 2, 14));
+        calendar.set_showed_date(CalendarDate::new(2024, 2, 20));
+        assert_eq!(calendar.showed_date(), CalendarDate::new(2024, 2, 1));
+
+        // The dates outlive the iterator they come from
+        assert_eq!(calendar.highlighted_dates(), []);
+        calendar.set_highlighted_dates((10..13).map(|day| CalendarDate::new(2024, 2, day)));
+        assert_eq!(
+            calendar.highlighted_dates(),
+            [
+                CalendarDate::new(2024, 2, 10),
+                CalendarDate::new(2024, 2, 11),
+                CalendarDate::new(2024, 2, 12),
+            ]
+        );
+
+        assert_eq!(calendar.pressed_date(), None);
+        calendar.on_date_clicked(|clicks, date| clicks.dates.push(date));
+
+        assert!(settle(lvgl, display));
+
+        // The day names take the first of the 7 rows, and the 1st of February 2024 is a Thursday.
+        // The middle of the calendar is the Wednesday of the third week.
+        input.click(lvgl, 105, 105);
+        assert_eq!(
+            calendar.pressed_date(),
+            Some(CalendarDate::new(2024, 2, 14))
+        );
+
+        // The arrows of the header change the month, which is not a date
+        let header = CalendarHeaderArrow::new(&mut calendar);
+        assert!(settle(lvgl, display));
+        let next = unsafe { lvgl_sys::lv_obj_get_child(&*header.raw, 2) };
+        let mut area = lvgl_sys::lv_area_t::default();
+        unsafe { lvgl_sys::lv_obj_get_coords(next, &mut area) };
+        input.click(lvgl, (area.x1 + area.x2) / 2, (area.y1 + area.y2) / 2);
+        assert!(settle(lvgl, display));
+        assert_eq!(calendar.showed_date(), CalendarDate::new(2024, 3, 1));
+
+        let clicks = screen.context().as_ref().unwrap();
+        assert_eq!(clicks.dates, [CalendarDate::new(2024, 2, 14)]);
+    });
+}
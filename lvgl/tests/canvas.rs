@@ -0,0 +1,70 @@
+use embedded_graphics_core::{pixelcolor::RgbColor, prelude::*, primitives::Rectangle};
+use lvgl::core::{ObjExt, PixelColor, Screen};
+use lvgl::draw::{LineDsc, RectDsc};
+use lvgl::style::Opacity;
+use lvgl::testing::{settle, with_screen};
+use lvgl::widgets::Canvas;
+
+#[test]
+fn canvas_draws_into_its_buffer() {
+    with_screen(64, 48, |lvgl, display, screen: &mut Screen<()>| {
+        let mut canvas = Canvas::with_size(screen, 32, 16);
+        canvas.set_pos(0, 0);
+        assert_eq!(canvas.size(), Size::new(32, 16));
+
+        canvas.fill_bg(PixelColor::BLUE, Opacity::OPA_COVER);
+        assert_eq!(canvas.get_px(31, 15), Some(PixelColor::BLUE));
+
+        canvas.set_px(1, 1, PixelColor::RED);
+        assert_eq!(canvas.get_px(1, 1), Some(PixelColor::RED));
+
+        // Out of the buffer
+        canvas.set_px(32, 0, PixelColor::RED).set_px(0, -1, PixelColor::RED);
+        assert_eq!(canvas.get_px(32, 0), None);
+        assert_eq!(canvas.get_px(0, 16), None);
+        assert_eq!(canvas.get_px(-1, 0), None);
+
+        let mut rect = RectDsc::default();
+        rect.set_bg_color(PixelColor::GREEN);
+        canvas.draw_rect(&Rectangle::new(Point::new(10, 2), Size::new(4, 4)), &rect);
+        assert_eq!(canvas.get_px(10, 2), Some(PixelColor::GREEN));
+        assert_eq!(canvas.get_px(13, 5), Some(PixelColor::GREEN));
+        assert_eq!(canvas.get_px(14, 5), Some(PixelColor::BLUE));
+
+        let mut line = LineDsc::default();
+        line.set_color(PixelColor::WHITE).set_width(1);
+        canvas.draw_line(&[Point::new(0, 10), Point::new(31, 10)], &line);
+        assert_eq!(canvas.get_px(16, 10), Some(PixelColor::WHITE));
+
+        // embedded_graphics
+        canvas
+            .fill_solid(
+                &Rectangle::new(Point::new(20, 12), Size::new(2, 2)),
+                PixelColor::RED,
+            )
+            .unwrap();
+        canvas
+            .draw_iter([
+                Pixel(Point::new(30, 2), PixelColor::GREEN),
+                Pixel(Point::new(99, 99), PixelColor::GREEN),
+            ])
+            .unwrap();
+        assert_eq!(canvas.get_px(21, 13), Some(PixelColor::RED));
+        assert_eq!(canvas.get_px(30, 2), Some(PixelColor::GREEN));
+
+        // No buffer to draw into
+        let mut empty = Canvas::new(screen);
+        empty.set_px(0, 0, PixelColor::RED);
+        assert_eq!(empty.get_px(0, 0), None);
+
+        assert!(settle(lvgl, display));
+        display.assert_pixel(31, 0, PixelColor::BLUE);
+        display.assert_pixel(10, 2, PixelColor::GREEN);
+        display.assert_pixel(40, 0, display.pixel(63, 47));
+
+        // The red square bleeds into its blue surroundings
+        canvas.blur(&Rectangle::new(Point::zero(), Size::new(32, 16)), 2);
+        assert_ne!(canvas.get_px(21, 13), Some(PixelColor::RED));
+        assert_ne!(canvas.get_px(23, 13), Some(PixelColor::BLUE));
+    });
+}
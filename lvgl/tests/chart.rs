@@ -0,0 +1,74 @@
+use embedded_graphics_core::pixelcolor::RgbColor;
+use lvgl::core::{ObjExt, PixelColor, Screen};
+use lvgl::style::Dir;
+use lvgl::testing::{settle, with_screen};
+use lvgl::widgets::{Chart, ChartAxis, ChartType, ChartUpdateMode};
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn chart_series_and_cursors() {
+    with_screen(100, 80, |lvgl, display, screen: &mut Screen<()>| {
+        let mut chart = Chart::new(screen);
+        chart.set_size(100, 80);
+        chart.set_type(ChartType::Bar);
+        chart.set_point_count(4);
+        chart.set_range(ChartAxis::PrimaryY, 0, 10);
+        assert_eq!(chart.chart_type(), ChartType::Bar);
+        assert_eq!(chart.point_count(), 4);
+
+        let mut series = chart.add_series(PixelColor::RED, ChartAxis::PrimaryY);
+        chart.set_values(&mut series, &[1, 2, 3]);
+        assert_eq!(chart.values(&series)[..3], [1, 2, 3]);
+
+        chart.set_value(&mut series, 3, 4);
+        assert_eq!(chart.values(&series), [1, 2, 3, 4]);
+
+        // The values are a ring buffer, the update mode only changes how they are drawn
+        chart.set_update_mode(ChartUpdateMode::Shift);
+        chart.push(&mut series, 5);
+        assert_eq!(chart.values(&series), [5, 2, 3, 4]);
+        assert_eq!(chart.x_start_point(&series), 1);
+
+        chart.set_update_mode(ChartUpdateMode::Circular);
+        chart.push(&mut series, 6);
+        assert_eq!(chart.values(&series), [5, 6, 3, 4]);
+        assert_eq!(chart.x_start_point(&series), 2);
+
+        chart.set_all(&mut series, 7);
+        assert_eq!(chart.values(&series), [7, 7, 7, 7]);
+
+        let mut cursor = chart.add_cursor(PixelColor::BLUE, Dir::HOR | Dir::VER);
+        chart.set_cursor_point(&mut cursor, &series, 2);
+
+        assert_eq!(chart.pressed_point(), None);
+
+        assert!(settle(lvgl, display));
+
+        // External arrays bound the number of points
+        let mut ext = chart.add_series(PixelColor::GREEN, ChartAxis::PrimaryY);
+        chart.set_ext_y_array(&mut ext, Box::leak(vec![1, 2, 3, 4, 5].into_boxed_slice()));
+        assert_eq!(chart.values(&ext), [1, 2, 3, 4]);
+        chart.set_point_count(5);
+        assert_eq!(chart.values(&ext), [1, 2, 3, 4, 5]);
+        let more = panic::catch_unwind(AssertUnwindSafe(|| {
+            chart.set_point_count(6);
+        }));
+        assert!(more.is_err());
+        assert_eq!(chart.point_count(), 5);
+
+        chart.remove_series(ext);
+        chart.set_point_count(6);
+        assert_eq!(chart.values(&series).len(), 6);
+
+        // Series and cursors are only used through their own chart
+        let mut other = Chart::new(screen);
+        let foreign = panic::catch_unwind(AssertUnwindSafe(|| {
+            other.push(&mut series, 1);
+        }));
+        assert!(foreign.is_err());
+        let foreign = panic::catch_unwind(AssertUnwindSafe(|| other.cursor_pos(&cursor)));
+        assert!(foreign.is_err());
+
+        chart.remove_series(series);
+    });
+}
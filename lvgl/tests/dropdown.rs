@@ -0,0 +1,84 @@
+use lvgl::core::{Group, Key, ObjExt, Screen};
+use lvgl::style::{Animation, Dir};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Dropdown, Roller, RollerMode};
+
+#[derive(Debug, Clone, Copy)]
+enum Speed {
+    Slow,
+    Normal,
+    Fast,
+}
+
+const SPEEDS: [Speed; 3] = [Speed::Slow, Speed::Normal, Speed::Fast];
+
+#[derive(Default)]
+struct Settings {
+    dropdown: Vec<usize>,
+    roller: Vec<usize>,
+}
+
+#[test]
+fn dropdown_and_roller_select_options() {
+    with_screen(200, 200, |lvgl, display, screen: &mut Screen<Settings>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        // Options can be built from anything that can be shown
+        let mut dropdown = Dropdown::new(screen);
+        dropdown.set_pos(0, 0);
+        dropdown.set_options(SPEEDS.iter().map(|speed| format!("{:?}", speed)));
+        assert_eq!(dropdown.option_count(), 3);
+        assert_eq!(
+            dropdown.options().collect::<Vec<_>>(),
+            ["Slow", "Normal", "Fast"]
+        );
+
+        dropdown.set_selected(2);
+        assert_eq!(dropdown.selected(), 2);
+        assert_eq!(dropdown.selected_str(), "Fast");
+        dropdown.set_selected(10);
+        assert_eq!(dropdown.selected(), 2);
+        dropdown.set_selected(0);
+
+        dropdown.set_dir(Dir::RIGHT);
+        assert_eq!(dropdown.dir(), Dir::RIGHT);
+        dropdown.set_dir(Dir::BOTTOM);
+        dropdown.on_selected(|settings, index| settings.dropdown.push(index));
+
+        // Infinite rollers repeat their options, but only have as many
+        let mut roller = Roller::new(screen);
+        roller.set_pos(100, 0);
+        roller.set_options(&["1", "2", "3", "4"], RollerMode::Infinite);
+        assert_eq!(roller.option_count(), 4);
+        assert_eq!(roller.options().collect::<Vec<_>>(), ["1", "2", "3", "4"]);
+        roller.set_selected(3, Animation::Off);
+        assert_eq!(roller.selected(), 3);
+        assert_eq!(roller.selected_str(), "4");
+
+        roller.set_options(&["Low", "High"], RollerMode::Normal);
+        assert_eq!(roller.selected(), 0);
+        roller.on_selected(|settings, index| settings.roller.push(index));
+
+        assert!(settle(lvgl, display));
+
+        let mut group = Group::new(lvgl);
+        group.add_obj(&mut dropdown);
+        group.add_obj(&mut roller);
+        input.set_group(&group);
+
+        // Open the list, pick the next option
+        input.press_key(lvgl, Key::Down);
+        input.press_key(lvgl, Key::Down);
+        input.press_key(lvgl, Key::Enter);
+        assert_eq!(dropdown.selected_str(), "Normal");
+
+        input.press_key(lvgl, Key::Next);
+        input.press_key(lvgl, Key::Down);
+        input.press_key(lvgl, Key::Enter);
+        assert_eq!(roller.selected_str(), "High");
+
+        let settings = screen.context().as_ref().unwrap();
+        assert_eq!(settings.dropdown, [1]);
+        assert_eq!(settings.roller, [1]);
+    });
+}
@@ -0,0 +1,122 @@
+use cstr_core::{CStr, CString};
+use lvgl::core::Lvgl;
+use lvgl::font::Font;
+use lvgl::fs::{DirEntry, Drive, FileSystem, FsError, MemFs, OpenMode, StdFs};
+use std::mem::MaybeUninit;
+
+fn read_file(path: &str) -> Result<Vec<u8>, lvgl_sys::lv_fs_res_t> {
+    let path = CString::new(path).unwrap();
+    unsafe {
+        let mut file = MaybeUninit::<lvgl_sys::lv_fs_file_t>::uninit();
+        let res = lvgl_sys::lv_fs_open(
+            file.as_mut_ptr(),
+            path.as_ptr(),
+            lvgl_sys::LV_FS_MODE_RD as lvgl_sys::lv_fs_mode_t,
+        );
+        if res != lvgl_sys::LV_FS_RES_OK as lvgl_sys::lv_fs_res_t {
+            return Err(res);
+        }
+        let mut data = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let mut n = 0;
+            lvgl_sys::lv_fs_read(
+                file.as_mut_ptr(),
+                buf.as_mut_ptr() as *mut cty::c_void,
+                buf.len() as u32,
+                &mut n,
+            );
+            if n == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..n as usize]);
+        }
+        lvgl_sys::lv_fs_close(file.as_mut_ptr());
+        Ok(data)
+    }
+}
+
+fn list_dir(path: &str) -> Vec<String> {
+    let path = CString::new(path).unwrap();
+    let mut names = Vec::new();
+    unsafe {
+        let mut dir = MaybeUninit::<lvgl_sys::lv_fs_dir_t>::uninit();
+        lvgl_sys::lv_fs_dir_open(dir.as_mut_ptr(), path.as_ptr());
+        loop {
+            let mut name = [0 as cty::c_char; lvgl_sys::LV_FS_MAX_FN_LENGTH as usize];
+            lvgl_sys::lv_fs_dir_read(dir.as_mut_ptr(), name.as_mut_ptr());
+            let name = std::ffi::CStr::from_ptr(name.as_ptr()).to_str().unwrap();
+            if name.is_empty() {
+                break;
+            }
+            names.push(name.to_string());
+        }
+        lvgl_sys::lv_fs_dir_close(dir.as_mut_ptr());
+    }
+    names
+}
+
+// LVGL is not thread safe, so everything runs in a single test
+#[test]
+fn drives_serve_files() {
+    let lvgl = Lvgl::new();
+
+    let mut assets = lvgl::include_assets!("tests/assets");
+    assert_eq!(
+        assets.get("/fonts/dummy.bin"),
+        Some(&b"not really a font"[..])
+    );
+    assert!(assets.open("hello.txt", OpenMode::WRITE).is_err());
+    let root = assets.dir_open("/").unwrap().collect::<Vec<_>>();
+    assert_eq!(
+        root,
+        vec![
+            DirEntry {
+                name: "fonts".into(),
+                is_dir: true
+            },
+            DirEntry {
+                name: "hello.txt".into(),
+                is_dir: false
+            },
+        ]
+    );
+
+    Drive::new(&lvgl, 'A', assets);
+
+    assert_eq!(
+        read_file("A:/hello.txt").unwrap(),
+        b"Hello from the assets\n"
+    );
+    assert_eq!(
+        read_file("A:fonts/dummy.bin").unwrap(),
+        b"not really a font"
+    );
+    assert!(read_file("A:/missing.txt").is_err());
+    assert_eq!(list_dir("A:/"), vec!["/fonts", "hello.txt"]);
+    assert_eq!(list_dir("A:/fonts"), vec!["dummy.bin"]);
+
+    // The host files are limited to the root of the drive
+    let root = std::env::temp_dir().join(format!("lvgl-fs-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("images")).unwrap();
+    std::fs::write(root.join("images/logo.bin"), b"not really an image").unwrap();
+    std::fs::write(root.join("secret.txt"), b"outside of the drive").unwrap();
+
+    let mut host = StdFs::new(root.join("images"));
+    assert!(matches!(
+        host.open("../secret.txt", OpenMode::READ),
+        Err(FsError::Denied)
+    ));
+    Drive::new(&lvgl, 'H', host);
+
+    assert_eq!(read_file("H:/logo.bin").unwrap(), b"not really an image");
+    assert!(read_file("H:/../secret.txt").is_err());
+    assert!(read_file("H:/missing.bin").is_err());
+    assert_eq!(list_dir("H:/"), vec!["logo.bin"]);
+
+    std::fs::remove_dir_all(root).unwrap();
+
+    // The data of Font::load() is only on its drive during the load
+    assert!(Font::load(b"not really a font").is_none());
+    assert!(Font::load_file(CStr::from_bytes_with_nul(b"\x01:0\0").unwrap()).is_none());
+}
@@ -0,0 +1,104 @@
+//! Renders the scenes of the examples and compares them with the PNGs of `tests/goldens`.
+//! Run with `LVGL_UPDATE_GOLDENS=1` to update the goldens after an intended change.
+
+use embedded_graphics_core::pixelcolor::RgbColor;
+use lvgl::core::{Display, Lvgl, ObjExt, PixelColor, Screen};
+use lvgl::style::{Align, State};
+use lvgl::testing::{assert_golden, settle, with_screen, HeadlessDisplay};
+use lvgl::widgets::{Arc, Bar, Btn, Label, Meter};
+
+const TOLERANCE: u8 = 4;
+
+fn golden(name: &str) -> String {
+    format!("{}/tests/goldens/{}.png", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn render(
+    lvgl: &mut Lvgl,
+    display: &mut Display<HeadlessDisplay>,
+    name: &str,
+    scene: impl FnOnce(&mut Screen<()>),
+) {
+    let mut screen = Screen::<()>::new(display);
+    scene(&mut screen);
+    display.load_screen(&mut screen);
+    assert!(settle(lvgl, display), "{} doesn't settle", name);
+    assert_golden(display, golden(name), TOLERANCE);
+}
+
+fn arc(screen: &mut Screen<()>) {
+    let mut arc = Arc::new(screen);
+    arc.set_size(150, 150);
+    arc.align_to(screen, Align::Center, 0, 10);
+    arc.set_bg_angles(0, 360);
+    arc.set_value(40);
+}
+
+fn bar(screen: &mut Screen<()>) {
+    let mut bar = Bar::new(screen);
+    bar.set_size(175, 20);
+    bar.align_to(screen, Align::Center, 0, 10);
+    bar.set_range(0, 100);
+    bar.set_value(40, lvgl_sys::lv_anim_enable_t_LV_ANIM_OFF);
+
+    let mut label = Label::new(screen);
+    label.set_text("Loading...");
+    label.align_to(&bar, Align::OutTopMid, 0, -10);
+}
+
+fn button_click(screen: &mut Screen<()>) {
+    let mut btn = Btn::new(screen);
+    btn.set_size(120, 50);
+    btn.align_to(screen, Align::Center, 0, 0);
+    btn.add_state(State::PRESSED);
+
+    let mut label = Label::new(&mut btn);
+    label.set_text("Click me!");
+    label.align_to(&btn, Align::Center, 0, 0);
+}
+
+fn gauge(screen: &mut Screen<()>) {
+    // Same scene as examples/gauge.rs
+    let mut gauge = Meter::new(screen);
+    gauge.set_size(200, 200);
+    gauge.align_to(screen, Align::Center, 0, 0);
+
+    let mut scale = gauge.add_scale();
+    gauge
+        .set_scale_ticks(&mut scale, 41, 2, 10, PixelColor::WHITE)
+        .set_scale_major_ticks(&mut scale, 8, 4, 15, PixelColor::BLACK, 10)
+        .set_scale_range(&mut scale, 0, 100, 270, 135);
+
+    let mut low = gauge.add_arc(&mut scale, 3, PixelColor::BLUE, 0);
+    gauge
+        .set_indicator_start_value(&mut low, 0)
+        .set_indicator_end_value(&mut low, 20);
+    let mut low_ticks =
+        gauge.add_scale_lines(&mut scale, PixelColor::BLUE, PixelColor::BLUE, false, 0);
+    gauge
+        .set_indicator_start_value(&mut low_ticks, 0)
+        .set_indicator_end_value(&mut low_ticks, 20);
+
+    let mut high = gauge.add_arc(&mut scale, 3, PixelColor::RED, 0);
+    gauge
+        .set_indicator_start_value(&mut high, 80)
+        .set_indicator_end_value(&mut high, 100);
+    let mut high_ticks =
+        gauge.add_scale_lines(&mut scale, PixelColor::RED, PixelColor::RED, false, 0);
+    gauge
+        .set_indicator_start_value(&mut high_ticks, 80)
+        .set_indicator_end_value(&mut high_ticks, 100);
+
+    let mut needle = gauge.add_needle_line(&mut scale, 4, PixelColor::BLACK, -10);
+    gauge.set_indicator_value(&mut needle, 50);
+}
+
+#[test]
+fn examples_match_goldens() {
+    with_screen(320, 240, |lvgl, display, _: &mut Screen<()>| {
+        render(lvgl, display, "arc", arc);
+        render(lvgl, display, "bar", bar);
+        render(lvgl, display, "button_click", button_click);
+        render(lvgl, display, "gauge", gauge);
+    });
+}
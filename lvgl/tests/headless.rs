@@ -0,0 +1,30 @@
+use embedded_graphics_core::pixelcolor::RgbColor;
+use lvgl::core::{PixelColor, Screen};
+use lvgl::testing::{settle, with_screen};
+
+#[test]
+fn headless_display_renders_screens() {
+    with_screen(64, 48, |lvgl, display, screen: &mut Screen<()>| {
+        unsafe {
+            lvgl_sys::lv_obj_set_style_bg_color(
+                &mut *screen.raw,
+                lvgl_sys::lv_color_make(255, 0, 0),
+                0,
+            );
+            lvgl_sys::lv_obj_set_style_bg_opa(
+                &mut *screen.raw,
+                lvgl_sys::LV_OPA_COVER as lvgl_sys::lv_opa_t,
+                0,
+            );
+        }
+
+        assert!(settle(lvgl, display));
+        display.assert_pixel(0, 0, PixelColor::RED);
+        display.assert_pixel(63, 47, PixelColor::RED);
+        assert_eq!(&display.to_raw()[..3], &[255, 0, 0]);
+        assert_eq!(&display.to_png()[..8], b"\x89PNG\r\n\x1a\n");
+
+        // Nothing changed, so nothing to redraw
+        assert!(settle(lvgl, display));
+    });
+}
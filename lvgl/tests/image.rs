@@ -0,0 +1,18 @@
+use cstr_core::CStr;
+use lvgl::image::ImageSource;
+
+// LVGL would read anything else as an image header
+#[test]
+fn image_sources_are_told_apart() {
+    let cstr = |bytes: &'static [u8]| CStr::from_bytes_with_nul(bytes).unwrap();
+
+    assert!(ImageSource::file(cstr(b"A:/logo.bin\0")).is_some());
+    assert!(ImageSource::file(cstr(b"\0")).is_none());
+    assert!(ImageSource::file(cstr(b"\x01:/logo.bin\0")).is_none());
+    assert!(ImageSource::file(cstr(b"\xef\x80\x8c\0")).is_none());
+
+    // LV_SYMBOL_OK
+    assert!(ImageSource::symbol(cstr(b"\xef\x80\x8c\0")).is_some());
+    assert!(ImageSource::symbol(cstr(b"\0")).is_none());
+    assert!(ImageSource::symbol(cstr(b"A:/logo.bin\0")).is_none());
+}
@@ -0,0 +1,68 @@
+use lvgl::core::{Event, Group, Key, ObjExt, Screen};
+use lvgl::style::Align;
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Btn, Slider};
+
+#[derive(Default)]
+struct Clicks {
+    first: u32,
+    second: u32,
+    long_presses: u32,
+    slides: u32,
+}
+
+#[test]
+fn scripted_input_triggers_callbacks() {
+    with_screen(240, 240, |lvgl, display, screen: &mut Screen<Clicks>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut first = Btn::new(screen);
+        first.set_size(100, 50);
+        first.align_to(screen, Align::TopMid, 0, 20);
+        first.on_event(Event::Clicked, |c| c.first += 1);
+        first.on_event(Event::LongPressed, |c| c.long_presses += 1);
+
+        let mut second = Btn::new(screen);
+        second.set_size(100, 50);
+        second.align_to(screen, Align::BottomMid, 0, -20);
+        second.on_event(Event::Clicked, |c| c.second += 1);
+
+        let mut slider = Slider::new(screen);
+        slider.set_size(160, 10);
+        slider.align_to(screen, Align::Center, 0, 0);
+        slider.on_event(Event::ValueChanged, |c| c.slides += 1);
+
+        assert!(settle(lvgl, display));
+
+        // Pointer: the first button spans (70, 20) to (170, 70)
+        input.click(lvgl, 120, 45);
+        assert_eq!(screen.context().as_ref().unwrap().first, 1);
+        input.click(lvgl, 10, 120);
+        assert_eq!(screen.context().as_ref().unwrap().first, 1);
+        input.long_press(lvgl, 120, 45);
+        assert_eq!(screen.context().as_ref().unwrap().long_presses, 1);
+        // The button is still clicked when released after a long press
+        let first_clicks = screen.context().as_ref().unwrap().first;
+        assert_eq!(first_clicks, 2);
+        // The slider spans (40, 115) to (200, 125)
+        input.drag(lvgl, (45, 120), (195, 120), 5);
+        assert!(screen.context().as_ref().unwrap().slides > 0);
+
+        let mut group = Group::new(lvgl);
+        group.add_obj(&mut first);
+        group.add_obj(&mut second);
+        input.set_group(&group);
+
+        // Keypad: the first button has the focus
+        input.press_key(lvgl, Key::Enter);
+        assert_eq!(screen.context().as_ref().unwrap().first, first_clicks + 1);
+        input.press_key(lvgl, Key::Next);
+        input.press_key(lvgl, Key::Enter);
+        assert_eq!(screen.context().as_ref().unwrap().second, 1);
+
+        // Encoder: turning moves the focus back to the first button
+        input.turn_encoder(lvgl, -1);
+        input.press_encoder(lvgl);
+        assert_eq!(screen.context().as_ref().unwrap().first, first_clicks + 2);
+    });
+}
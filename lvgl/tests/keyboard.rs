@@ -0,0 +1,108 @@
+use lvgl::core::{ObjExt, Screen};
+use lvgl::style::Flag;
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{keys, BtnmatrixCtrl, Keyboard, KeyboardMode, Textarea};
+
+#[derive(Default)]
+struct Typing {
+    ready: u32,
+    cancelled: u32,
+}
+
+#[test]
+fn keyboard_types_with_custom_layouts() {
+    with_screen(200, 200, |lvgl, display, screen: &mut Screen<Typing>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut textarea = Textarea::new(screen);
+        textarea.set_size(200, 50);
+        textarea.set_pos(0, 0);
+
+        // A hex keypad on the bottom half of the screen: 5 units per row, 40 pixels each
+        let mut keyboard = Keyboard::new(screen);
+        keyboard.set_size(200, 100);
+        keyboard.set_pos(0, 100);
+        keyboard.set_map(
+            KeyboardMode::User1,
+            &[
+                &["0", "1", "2", "3", "4"],
+                &["5", "6", "7", "8", "9"],
+                &["A", "B", "C", "D", "E"],
+                &["F", keys::BACKSPACE, keys::OK, keys::CLOSE],
+            ],
+            &[
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::width(2),
+                BtnmatrixCtrl::NO_REPEAT | BtnmatrixCtrl::CLICK_TRIG,
+                BtnmatrixCtrl::NO_REPEAT | BtnmatrixCtrl::CLICK_TRIG,
+            ],
+        );
+        keyboard.set_mode(KeyboardMode::User1);
+        assert_eq!(keyboard.mode(), KeyboardMode::User1);
+
+        keyboard.set_textarea(&textarea);
+        keyboard.on_ready(|c| c.ready += 1);
+        keyboard.on_cancel(|c| c.cancelled += 1);
+        assert!(keyboard.has_textarea());
+
+        assert!(settle(lvgl, display));
+
+        // Rows are 25 pixels high
+        input.click(lvgl, 20, 162);
+        input.click(lvgl, 60, 112);
+        input.click(lvgl, 180, 137);
+        assert_eq!(textarea.text(), "A19");
+
+        input.click(lvgl, 80, 187);
+        assert_eq!(textarea.text(), "A1");
+
+        input.click(lvgl, 140, 187);
+        input.click(lvgl, 180, 187);
+        let typing = screen.context().as_ref().unwrap();
+        assert_eq!((typing.ready, typing.cancelled), (1, 1));
+
+        // Replacing a layout shows it on all the keyboards in its mode
+        let mut other = Keyboard::new(screen);
+        other.set_mode(KeyboardMode::User1).add_flag(Flag::HIDDEN);
+        keyboard.set_map(
+            KeyboardMode::User1,
+            &[&["X", "Y"], &[keys::OK]],
+            &[
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+                BtnmatrixCtrl::empty(),
+            ],
+        );
+        for kb in [&mut keyboard, &mut other] {
+            let text = unsafe { lvgl_sys::lv_btnmatrix_get_btn_text(&mut *kb.raw, 0) };
+            assert_eq!(unsafe { std::ffi::CStr::from_ptr(text) }.to_str(), Ok("X"));
+        }
+
+        // Typing into the same text area again
+        keyboard.set_textarea(&textarea);
+        assert!(settle(lvgl, display));
+        input.click(lvgl, 50, 112);
+        assert_eq!(textarea.text(), "A1X");
+
+        // The keyboards forget a deleted text area
+        other.set_textarea(&textarea);
+        unsafe { lvgl_sys::lv_obj_del(&mut *textarea.raw) };
+        assert!(!keyboard.has_textarea());
+        assert!(!other.has_textarea());
+    });
+}
@@ -0,0 +1,49 @@
+use cstr_core::CStr;
+use lvgl::core::{ObjExt, Screen};
+use lvgl::testing::{settle, with_screen};
+use lvgl::widgets::{Label, LongMode};
+
+#[test]
+fn label_edits_and_hit_tests_text() {
+    with_screen(200, 100, |lvgl, display, screen: &mut Screen<()>| {
+        let mut label = Label::new(screen);
+        label.set_pos(0, 0);
+
+        label.set_text("Hello world");
+        label.ins_text(5, ",");
+        assert_eq!(label.text(), "Hello, world");
+        label.cut_text(0, 7);
+        assert_eq!(label.text(), "world");
+
+        // Like in C, texts end at their first nul byte
+        label.set_text("Hello\0world");
+        assert_eq!(label.text(), "Hello");
+
+        // LVGL points to static texts rather than copying them
+        let text: &'static CStr = CStr::from_bytes_with_nul(b"Static text\0").unwrap();
+        label.set_text_static(text);
+        assert_eq!(label.text(), "Static text");
+
+        assert_eq!(label.long_mode(), LongMode::Wrap);
+        label.set_long_mode(LongMode::Clip);
+        assert_eq!(label.long_mode(), LongMode::Clip);
+
+        label.set_recolor(true);
+        assert!(label.recolor());
+
+        assert_eq!(label.text_selection(), None);
+        label.set_text_sel_start(4).set_text_sel_end(1);
+        assert_eq!(label.text_selection(), Some((1, 4)));
+
+        assert!(settle(lvgl, display));
+
+        // The characters are laid out from left to right on a single line
+        let (x0, y0) = label.letter_pos(0);
+        let (x2, y2) = label.letter_pos(2);
+        assert!(x0 < x2);
+        assert_eq!(y0, y2);
+        assert_eq!(label.letter_on(x2 + 1, y2 + 1), 2);
+        assert!(label.is_char_under_pos(x2 + 1, y2 + 1));
+        assert!(!label.is_char_under_pos(190, y2 + 1));
+    });
+}
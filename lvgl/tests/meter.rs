@@ -0,0 +1,53 @@
+use embedded_graphics_core::pixelcolor::RgbColor;
+use lvgl::core::{ObjExt, PixelColor, Screen};
+use lvgl::testing::{settle, with_screen};
+use lvgl::widgets::Meter;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn meter_scales_and_indicators() {
+    with_screen(120, 120, |lvgl, display, screen: &mut Screen<()>| {
+        let mut meter = Meter::new(screen);
+        meter.set_size(120, 120);
+
+        let mut scale = meter.add_scale();
+        meter
+            .set_scale_ticks(&mut scale, 11, 2, 5, PixelColor::BLACK)
+            .set_scale_major_ticks(&mut scale, 5, 3, 8, PixelColor::BLACK, 5)
+            .set_scale_range(&mut scale, -50, 50, 270, 135);
+        assert_eq!(meter.scale_range(&scale), (-50, 50));
+
+        let mut needle = meter.add_needle_line(&mut scale, 2, PixelColor::RED, -5);
+        meter.set_indicator_value(&mut needle, 20);
+        assert_eq!(meter.indicator_values(&needle), (20, 20));
+
+        // Values are clamped to the scale
+        meter.set_indicator_value(&mut needle, 80);
+        assert_eq!(meter.indicator_values(&needle), (50, 50));
+
+        let mut arc = meter.add_arc(&mut scale, 4, PixelColor::BLUE, 0);
+        meter
+            .set_indicator_start_value(&mut arc, -70)
+            .set_indicator_end_value(&mut arc, 10);
+        assert_eq!(meter.indicator_values(&arc), (-50, 10));
+
+        let mut lines =
+            meter.add_scale_lines(&mut scale, PixelColor::GREEN, PixelColor::RED, true, 1);
+        meter
+            .set_indicator_start_value(&mut lines, 0)
+            .set_indicator_end_value(&mut lines, 50);
+
+        // Scales and indicators are only used through their own meter
+        let mut other = Meter::new(screen);
+        let foreign = panic::catch_unwind(AssertUnwindSafe(|| {
+            other.set_indicator_value(&mut needle, 0);
+        }));
+        assert!(foreign.is_err());
+        let foreign = panic::catch_unwind(AssertUnwindSafe(|| {
+            other.add_arc(&mut scale, 4, PixelColor::BLUE, 0);
+        }));
+        assert!(foreign.is_err());
+
+        assert!(settle(lvgl, display));
+    });
+}
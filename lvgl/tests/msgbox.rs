@@ -0,0 +1,70 @@
+use lvgl::core::{Event, ObjExt, Screen};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Btn, Msgbox};
+
+#[derive(Default)]
+struct Dialog {
+    answers: Vec<(u16, String)>,
+    background_clicks: u32,
+}
+
+fn child_count(obj: &lvgl_sys::lv_obj_t) -> u32 {
+    unsafe { lvgl_sys::lv_obj_get_child_cnt(obj) }
+}
+
+#[test]
+fn msgbox_reports_clicked_buttons() {
+    with_screen(240, 240, |lvgl, display, screen: &mut Screen<Dialog>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut background = Btn::new(screen);
+        background.set_size(240, 240);
+        background.set_pos(0, 0);
+        background.on_event(Event::Clicked, |dialog| dialog.background_clicks += 1);
+
+        // Closing deletes the message box
+        let info = Msgbox::new(screen, "Info", "Saved", &[], true);
+        assert_eq!(child_count(&*screen.raw), 2);
+        info.close();
+        assert_eq!(child_count(&*screen.raw), 1);
+
+        let top_layer = unsafe { &*lvgl_sys::lv_layer_top() };
+        let mut confirm = Msgbox::modal(screen, "Delete", "Are you sure?", &["Yes", "No"], false);
+        assert_eq!(child_count(top_layer), 1);
+        assert_eq!(confirm.active_btn(), None);
+        confirm.on_btn_clicked(|dialog, btn_id, text| {
+            dialog.answers.push((btn_id, text.to_string()));
+            btn_id == 0
+        });
+        assert!(settle(lvgl, display));
+
+        // The backdrop takes the clicks around the message box
+        input.click(lvgl, 2, 2);
+        assert_eq!(screen.context().as_ref().unwrap().background_clicks, 0);
+
+        // The buttons share the width of their matrix
+        let btns = confirm.btns().unwrap();
+        let mut area = lvgl_sys::lv_area_t::default();
+        unsafe { lvgl_sys::lv_obj_get_coords(&*btns.raw, &mut area) };
+        let y = (area.y1 + area.y2) / 2;
+        let quarter = (area.x2 - area.x1) / 4;
+
+        input.click(lvgl, area.x2 - quarter, y);
+        assert_eq!(confirm.active_btn(), Some(1));
+        assert_eq!(confirm.active_btn_text(), Some("No"));
+        assert!(settle(lvgl, display));
+        assert_eq!(child_count(top_layer), 1);
+
+        // Yes closes it, with its backdrop
+        input.click(lvgl, area.x1 + quarter, y);
+        assert!(settle(lvgl, display));
+        assert_eq!(child_count(top_layer), 0);
+
+        let dialog = screen.context().as_ref().unwrap();
+        assert_eq!(
+            dialog.answers,
+            [(1, "No".to_string()), (0, "Yes".to_string())]
+        );
+        assert_eq!(dialog.background_clicks, 0);
+    });
+}
@@ -0,0 +1,56 @@
+use embedded_graphics_core::{image::ImageDrawable, pixelcolor::RgbColor};
+use lvgl::core::{ObjExt, PixelColor, Screen};
+use lvgl::image::{ColorFormat, ImageDsc};
+use lvgl::testing::{settle, with_screen, HeadlessDisplay};
+
+static ALPHA: ImageDsc = ImageDsc::new(ColorFormat::Alpha8Bit, 2, 2, &[0xff; 4]);
+
+#[test]
+fn snapshots_render_objects() {
+    with_screen(64, 48, |lvgl, display, screen: &mut Screen<()>| {
+        unsafe {
+            lvgl_sys::lv_obj_set_style_bg_color(
+                &mut *screen.raw,
+                lvgl_sys::lv_color_make(255, 0, 0),
+                0,
+            );
+            lvgl_sys::lv_obj_set_style_bg_opa(
+                &mut *screen.raw,
+                lvgl_sys::LV_OPA_COVER as lvgl_sys::lv_opa_t,
+                0,
+            );
+        }
+        assert!(settle(lvgl, display));
+
+        let snapshot = screen.snapshot(ColorFormat::TrueColor).unwrap();
+        assert_eq!((snapshot.width(), snapshot.height()), (64, 48));
+        assert_eq!(snapshot.pixel(63, 47), Some(PixelColor::RED));
+        assert_eq!(snapshot.pixel(64, 0), None);
+
+        let mut copy = HeadlessDisplay::new(64, 48);
+        snapshot.draw(&mut copy).unwrap();
+        assert_eq!(copy.to_raw(), display.to_raw());
+
+        if let Some(raw) = snapshot.as_image_raw() {
+            let mut copy = HeadlessDisplay::new(64, 48);
+            raw.draw(&mut copy).unwrap();
+            assert_eq!(copy.to_raw(), display.to_raw());
+        }
+
+        let mut buf = vec![0; screen.snapshot_buf_size(ColorFormat::TrueColor)];
+        let snapshot = screen
+            .snapshot_to_buf(ColorFormat::TrueColor, &mut buf)
+            .unwrap();
+        assert_eq!(snapshot.pixel(0, 0), Some(PixelColor::RED));
+        assert!(screen
+            .snapshot_to_buf(ColorFormat::TrueColor, &mut [0; 16])
+            .is_none());
+
+        // Only the pixels of true color images can be read
+        assert_eq!(ALPHA.pixel(0, 0), None);
+        assert!(ALPHA.as_image_raw().is_none());
+        let mut copy = HeadlessDisplay::new(64, 48);
+        ALPHA.draw(&mut copy).unwrap();
+        assert_eq!(copy.pixel(0, 0), PixelColor::default());
+    });
+}
@@ -0,0 +1,61 @@
+use lvgl::core::{Group, Key, ObjExt, Screen};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{CellCtrl, Table};
+
+#[derive(Default)]
+struct Clicks {
+    cells: Vec<(u16, u16)>,
+}
+
+#[test]
+fn table_reports_clicked_cells() {
+    with_screen(200, 200, |lvgl, display, screen: &mut Screen<Clicks>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut table = Table::new(screen);
+        table.set_pos(0, 0);
+        table.set_col_widths(&[60, 60, 60]);
+        assert_eq!(table.col_count(), 3);
+        assert_eq!(table.col_width(1), 60);
+
+        // The shorter row gets an empty cell
+        table.set_data(&[
+            &["Name", "Qty", "Price"],
+            &["Apple", "3"],
+            &["Pear", "1", "2.00"],
+        ]);
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.col_count(), 3);
+        assert_eq!(table.cell_value(1, 0), "Apple");
+        assert_eq!(table.cell_value(1, 2), "");
+        assert_eq!(table.cell_value(2, 2), "2.00");
+
+        table.add_cell_ctrl(0, 0, CellCtrl::MERGE_RIGHT | CellCtrl::TEXT_CROP);
+        assert!(table.has_cell_ctrl(0, 0, CellCtrl::MERGE_RIGHT));
+        table.clear_cell_ctrl(0, 0, CellCtrl::MERGE_RIGHT);
+        assert!(!table.has_cell_ctrl(0, 0, CellCtrl::MERGE_RIGHT));
+        assert!(table.has_cell_ctrl(0, 0, CellCtrl::TEXT_CROP));
+
+        assert_eq!(table.selected_cell(), None);
+        table.on_cell_clicked(|clicks, row, col| clicks.cells.push((row, col)));
+
+        assert!(settle(lvgl, display));
+
+        // Columns are 60 pixels wide, the first row starts at the top. Cells are only selected
+        // while pressed.
+        input.click(lvgl, 90, 5);
+        assert_eq!(table.selected_cell(), None);
+
+        // The first key selects the first cell
+        let mut group = Group::new(lvgl);
+        group.add_obj(&mut table);
+        input.set_group(&group);
+        input.press_key(lvgl, Key::Down);
+        assert_eq!(table.selected_cell(), Some((0, 0)));
+        input.press_key(lvgl, Key::Down);
+        assert_eq!(table.selected_cell(), Some((1, 0)));
+
+        let clicks = screen.context().as_ref().unwrap();
+        assert_eq!(clicks.cells, [(0, 1), (1, 0)]);
+    });
+}
@@ -0,0 +1,69 @@
+use lvgl::core::{ObjExt, Screen};
+use lvgl::style::{Animation, Dir};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{Label, Tabview, Tileview};
+
+#[derive(Default)]
+struct Navigation {
+    tabs: Vec<usize>,
+    tiles: Vec<(u8, u8)>,
+}
+
+#[test]
+fn tabview_and_tileview_navigate() {
+    with_screen(
+        300,
+        200,
+        |lvgl, display, screen: &mut Screen<Navigation>| {
+            let mut input = TestInput::new(lvgl, display);
+
+            // Three tabs of 100 pixels wide, with their buttons on top
+            let mut tabview = Tabview::new(screen, Dir::TOP, 40);
+            tabview.set_size(300, 100);
+            tabview.set_pos(0, 0);
+            for name in &["First", "Second", "Third"] {
+                let mut tab = tabview.add_tab(name);
+                Label::new(&mut tab).set_text(*name);
+            }
+            assert_eq!(tabview.tab_count(), 3);
+            assert_eq!(tabview.active_tab(), 0);
+            tabview.rename_tab(2, "Last");
+
+            tabview.set_act(10, Animation::Off);
+            assert_eq!(tabview.active_tab(), 2);
+            tabview.set_act(0, Animation::Off);
+            tabview.on_tab_changed(|navigation, tab| navigation.tabs.push(tab));
+
+            // Two tiles side by side, swiped between
+            let mut tileview = Tileview::new(screen);
+            tileview.set_size(300, 100);
+            tileview.set_pos(0, 100);
+            let mut first = tileview.add_tile(0, 0, Dir::RIGHT);
+            Label::new(&mut first).set_text("First");
+            let mut second = tileview.add_tile(1, 0, Dir::LEFT);
+            Label::new(&mut second).set_text("Second");
+            assert_eq!(tileview.active_tile(), None);
+
+            tileview.set_act(1, 0, Animation::Off);
+            assert_eq!(tileview.active_tile(), Some((1, 0)));
+            tileview.set_act_tile(&mut first, Animation::Off);
+            assert_eq!(tileview.active_tile(), Some((0, 0)));
+            tileview.on_tile_changed(|navigation, col, row| navigation.tiles.push((col, row)));
+
+            assert!(settle(lvgl, display));
+
+            // Only the tabs picked by the user are reported
+            input.click(lvgl, 150, 20);
+            assert!(settle(lvgl, display));
+            assert_eq!(tabview.active_tab(), 1);
+
+            input.drag(lvgl, (250, 150), (50, 150), 10);
+            assert!(settle(lvgl, display));
+            assert_eq!(tileview.active_tile(), Some((1, 0)));
+
+            let navigation = screen.context().as_ref().unwrap();
+            assert_eq!(navigation.tabs, [1]);
+            assert_eq!(navigation.tiles, [(1, 0)]);
+        },
+    );
+}
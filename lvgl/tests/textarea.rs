@@ -0,0 +1,74 @@
+use lvgl::core::{Group, Key, ObjExt, Screen};
+use lvgl::testing::{settle, with_screen, TestInput};
+use lvgl::widgets::{InsertAction, Textarea};
+
+#[derive(Default)]
+struct Inserts {
+    seen: Vec<String>,
+}
+
+#[test]
+fn textarea_filters_and_replaces_text() {
+    with_screen(200, 100, |lvgl, display, screen: &mut Screen<Inserts>| {
+        let mut input = TestInput::new(lvgl, display);
+
+        let mut textarea = Textarea::new(screen);
+        textarea.set_size(200, 50);
+        textarea.set_pos(0, 0);
+
+        // Positions are in characters, not bytes
+        textarea.set_text("héllo");
+        assert_eq!(textarea.text(), "héllo");
+        assert_eq!(textarea.cursor_pos(), 5);
+        textarea.set_cursor_pos(1).add_text("ü");
+        assert_eq!(textarea.text(), "hüéllo");
+        assert_eq!(textarea.cursor_pos(), 2);
+
+        assert_eq!(textarea.selection(), None);
+        textarea.select(1, 3);
+        assert_eq!(textarea.selection(), Some((1, 3)));
+        textarea.clear_selection();
+        assert_eq!(textarea.selection(), None);
+
+        textarea.set_placeholder_text("Hex code");
+        assert_eq!(textarea.placeholder_text(), "Hex code");
+
+        // Uppercase hex digits only, lowercase ones are turned into uppercase ones
+        textarea.set_text("");
+        textarea.set_accepted_chars("0123456789ABCDEF");
+        textarea.set_max_length(4);
+        assert_eq!(textarea.max_length(), 4);
+        textarea.on_insert(|inserts, text| {
+            inserts.seen.push(text.to_string());
+            match text {
+                "x" => InsertAction::Veto,
+                "a" | "b" | "c" | "d" | "e" | "f" => InsertAction::Replace(text.to_uppercase()),
+                _ => InsertAction::Keep,
+            }
+        });
+
+        assert!(settle(lvgl, display));
+
+        let mut group = Group::new(lvgl);
+        group.add_obj(&mut textarea);
+        input.set_group(&group);
+
+        for c in "axg1234".chars() {
+            input.press_key(lvgl, c);
+        }
+        assert_eq!(textarea.text(), "A123");
+        input.press_key(lvgl, Key::Backspace);
+        textarea.add_text("ff");
+        assert_eq!(textarea.text(), "A12F");
+
+        // Replacements and deletions are not seen, rejected characters are
+        let inserts = screen.context().as_ref().unwrap();
+        assert_eq!(inserts.seen, ["a", "x", "g", "1", "2", "3", "4", "f", "f"]);
+
+        textarea.clear_accepted_chars();
+        textarea.set_password_mode(true);
+        textarea.set_password_bullet("•");
+        assert!(textarea.password_mode());
+        assert_eq!(textarea.text(), "A12F");
+    });
+}